@@ -7,3 +7,9 @@ pub const TOKEN_DECIMALS: u32 = 9;
 pub const PAYOUT_PER_SHARE: u64 = 10u64.pow(TOKEN_DECIMALS);
 
 pub const SCALE: u64 = 1000000;
+
+/// Outcome index of the LONG token in a scalar market, SHORT is the other outcome
+pub const SCALAR_LONG_INDEX: usize = 0;
+
+/// Upper bound on any fee, in basis points
+pub const MAX_FEE_BPS: u64 = 10000;
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SHARES_DECIMALS, TOKEN_DECIMALS};
+use crate::curves::PricingCurve;
+use crate::error::CustomError;
+use crate::utils::{calculate_cpmm_cost, calculate_cpmm_price, check_cpmm_invariant};
+
+/// Fixed-product market maker for two-outcome markets, implemented as its cost-function
+/// equivalent. No pool balances are stored on chain: the market maker's funds stand in for
+/// the complete sets backing the pool, whose implied balances `funds - q_i` keep a product of `b²`.
+pub struct Cpmm {
+    pub b: u64,
+}
+
+impl PricingCurve for Cpmm {
+    fn cost(&self, q: &[u64]) -> Result<u64> {
+        calculate_cpmm_cost(q, self.b)
+    }
+
+    fn price(&self, q: &[u64], outcome_index: usize) -> Result<u64> {
+        calculate_cpmm_price(q, self.b, outcome_index)
+    }
+
+    fn max_loss(&self, num_outcomes: usize) -> Result<u64> {
+        require!(num_outcomes == 2, CustomError::InvalidCurveOutcomes);
+
+        // The implied pool starts with `b` complete sets
+        self.b
+            .checked_mul(10u64.pow(TOKEN_DECIMALS - SHARES_DECIMALS))
+            .ok_or(error!(CustomError::Overflow))
+    }
+
+    fn check_invariant(&self, q: &[u64], reserves: u64) -> Result<()> {
+        check_cpmm_invariant(q, self.b, reserves)
+    }
+}
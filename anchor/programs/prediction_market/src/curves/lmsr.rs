@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SHARES_DECIMALS, TOKEN_DECIMALS};
+use crate::curves::PricingCurve;
+use crate::error::CustomError;
+use crate::utils::{calculate_cost, calculate_price, calculate_required_initial_funds};
+
+/// Hanson's logarithmic market scoring rule with a fixed liquidity parameter
+pub struct Lmsr {
+    pub b: u64,
+}
+
+impl PricingCurve for Lmsr {
+    fn cost(&self, q: &[u64]) -> Result<u64> {
+        calculate_cost(q, self.b)
+    }
+
+    fn price(&self, q: &[u64], outcome_index: usize) -> Result<u64> {
+        calculate_price(q, self.b, outcome_index)
+    }
+
+    fn max_loss(&self, num_outcomes: usize) -> Result<u64> {
+        calculate_required_initial_funds(self.b, num_outcomes)
+    }
+
+    fn inverse_cost(&self, q: &[u64], outcome_index: usize, amount: u64) -> Result<u64> {
+        require!(outcome_index < q.len(), CustomError::InvalidOutcome);
+
+        // Solve C(q') = C(q) + amount for q'_i in closed form
+        let b_f64 = self.b as f64;
+        let scale_factor = 10u64.pow(TOKEN_DECIMALS - SHARES_DECIMALS) as f64;
+        let target = (self.cost(q)? as f64 + amount as f64) / scale_factor;
+        let max_exp = q.iter().map(|&qi| qi as f64 / b_f64).fold(target / b_f64, f64::max);
+        let others: f64 = q
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != outcome_index)
+            .map(|(_, &qj)| (qj as f64 / b_f64 - max_exp).exp())
+            .sum();
+        let remainder = (target / b_f64 - max_exp).exp() - others;
+        if remainder <= 0.0 {
+            return Ok(0);
+        }
+        let q_i = b_f64 * (max_exp + remainder.ln());
+        let mut num_shares = (q_i - q[outcome_index] as f64).max(0.0).floor() as u64;
+
+        // Rounding in the cost function may leave the estimate one share too high
+        while num_shares > 0 && self.buy_cost(q, outcome_index, num_shares)? > amount {
+            num_shares -= 1;
+        }
+        Ok(num_shares)
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::PricingCurve;
+use crate::utils::{calculate_ls_lmsr_cost, calculate_ls_lmsr_price, calculate_required_initial_funds};
+
+/// Liquidity-sensitive LMSR (Othman et al.), `b(q) = max(b, alpha * Σ q_i)`
+pub struct LsLmsr {
+    pub b: u64,
+    pub alpha: u64,
+}
+
+impl PricingCurve for LsLmsr {
+    fn cost(&self, q: &[u64]) -> Result<u64> {
+        calculate_ls_lmsr_cost(q, self.b, self.alpha)
+    }
+
+    fn price(&self, q: &[u64], outcome_index: usize) -> Result<u64> {
+        calculate_ls_lmsr_price(q, self.b, self.alpha, outcome_index)
+    }
+
+    fn max_loss(&self, num_outcomes: usize) -> Result<u64> {
+        // The worst case is the cost at q = 0, where the floor on b applies
+        calculate_required_initial_funds(self.b, num_outcomes)
+    }
+}
@@ -0,0 +1,86 @@
+pub mod cpmm;
+pub mod lmsr;
+pub mod ls_lmsr;
+
+use anchor_lang::prelude::*;
+
+use crate::error::CustomError;
+use crate::state::market::CurveType;
+
+pub use cpmm::Cpmm;
+pub use lmsr::Lmsr;
+pub use ls_lmsr::LsLmsr;
+
+/// Automated market maker pricing rule shared by all trade handlers.
+/// Costs are in base token units, prices are scaled by `SCALE`.
+pub trait PricingCurve {
+    /// Value of the cost function for the share vector `q`
+    fn cost(&self, q: &[u64]) -> Result<u64>;
+
+    /// Marginal price of `outcome_index` at `q`
+    fn price(&self, q: &[u64], outcome_index: usize) -> Result<u64>;
+
+    /// Worst-case loss of the market maker for a market with `num_outcomes`
+    fn max_loss(&self, num_outcomes: usize) -> Result<u64>;
+
+    /// Checks curve-specific invariants against the market maker's `reserves`
+    fn check_invariant(&self, _q: &[u64], _reserves: u64) -> Result<()> {
+        Ok(())
+    }
+
+    /// Largest number of shares of `outcome_index` that `amount` can buy at `q`
+    fn inverse_cost(&self, q: &[u64], outcome_index: usize, amount: u64) -> Result<u64> {
+        // Grow an upper bound, then bisect on the buy cost
+        let mut high: u64 = 1;
+        while self.buy_cost(q, outcome_index, high)? <= amount {
+            high = high.checked_mul(2).ok_or(CustomError::Overflow)?;
+        }
+        let mut low: u64 = 0;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.buy_cost(q, outcome_index, mid)? <= amount {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Cost of buying `num_shares` of `outcome_index` at `q`
+    fn buy_cost(&self, q: &[u64], outcome_index: usize, num_shares: u64) -> Result<u64> {
+        require!(outcome_index < q.len(), CustomError::InvalidOutcome);
+
+        let mut q_after = q.to_vec();
+        q_after[outcome_index] = q_after[outcome_index]
+            .checked_add(num_shares)
+            .ok_or(CustomError::Overflow)?;
+
+        self.cost(&q_after)?
+            .checked_sub(self.cost(q)?)
+            .ok_or(error!(CustomError::MathError))
+    }
+
+    /// Refund for selling `num_shares` of `outcome_index` at `q`
+    fn sell_refund(&self, q: &[u64], outcome_index: usize, num_shares: u64) -> Result<u64> {
+        require!(outcome_index < q.len(), CustomError::InvalidOutcome);
+
+        let mut q_after = q.to_vec();
+        q_after[outcome_index] = q_after[outcome_index]
+            .checked_sub(num_shares)
+            .ok_or(CustomError::MathError)?;
+
+        self.cost(q)?
+            .checked_sub(self.cost(&q_after)?)
+            .ok_or(error!(CustomError::MathError))
+    }
+}
+
+/// Builds the pricing curve selected by `curve_type`
+pub fn pricing_curve(curve_type: CurveType, b: u64, alpha: u64) -> Box<dyn PricingCurve> {
+    match curve_type {
+        CurveType::Lmsr => Box::new(Lmsr { b }),
+        CurveType::LsLmsr => Box::new(LsLmsr { b, alpha }),
+        CurveType::Cpmm => Box::new(Cpmm { b }),
+    }
+}
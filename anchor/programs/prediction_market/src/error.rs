@@ -6,7 +6,7 @@ pub enum CustomError {
     NoOutcomes,
     #[msg("Liquidity parameter b must be greater than zero")]
     InvalidB,
-    #[msg("Duration must be positive")]
+    #[msg("Trading must end after it starts and after the current time")]
     InvalidDuration,
     #[msg("Invalid owner for the mint account.")]
     InvalidOwner,
@@ -57,6 +57,93 @@ pub enum CustomError {
 
     #[msg("Market is not settled yet")]
     MarketNotSettled,
+
+    #[msg("LS-LMSR alpha must be between zero and one")]
+    InvalidAlpha,
+    #[msg("Initial funds do not cover the market maker's worst-case loss")]
+    InsufficientInitialFunds,
+    #[msg("Constant-product markets require exactly two outcomes")]
+    InvalidCurveOutcomes,
+    #[msg("Market maker invariant violated")]
+    InvariantViolated,
+    #[msg("Scalar markets need two outcomes and a lower bound below the upper bound")]
+    InvalidScalarBounds,
+    #[msg("Instruction does not apply to this market type")]
+    InvalidMarketType,
+    #[msg("Payouts need one numerator per outcome and a non-zero sum")]
+    InvalidPayouts,
+    #[msg("Challenge period must not be negative and disputes need a bond and an arbitrator")]
+    InvalidDisputeConfig,
+    #[msg("An outcome has already been proposed")]
+    OutcomeAlreadyProposed,
+    #[msg("No outcome has been proposed")]
+    OutcomeNotProposed,
+    #[msg("Challenge period has ended")]
+    ChallengePeriodEnded,
+    #[msg("Outcome is already disputed")]
+    OutcomeAlreadyDisputed,
+    #[msg("Outcome is not disputed")]
+    OutcomeNotDisputed,
+    #[msg("Instruction does not apply to this resolution mode")]
+    InvalidResolutionMode,
+    #[msg("Challenge period has not ended")]
+    ChallengePeriodNotEnded,
+    #[msg("Resolution already finished")]
+    ResolutionAlreadyFinished,
+    #[msg("Committee needs unique members and a threshold between one and the member count")]
+    InvalidCommittee,
+    #[msg("Signer is not a committee member")]
+    NotCommitteeMember,
+    #[msg("Price feed settings are invalid")]
+    InvalidPriceFeedConfig,
+    #[msg("Price feed account is invalid")]
+    InvalidPriceFeed,
+    #[msg("Price is not trading")]
+    PriceNotTrading,
+    #[msg("Price was published before the market ended or is stale")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Trading has not started yet")]
+    MarketNotOpen,
+    #[msg("Market is paused")]
+    MarketPaused,
+    #[msg("Market is not paused")]
+    MarketNotPaused,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Fee exceeds the allowed maximum")]
+    FeeTooHigh,
+    #[msg("Base mint is not allowed")]
+    MintNotAllowed,
+    #[msg("Allowed mints must be unique and at most ten")]
+    InvalidAllowedMints,
+    #[msg("Fee split must add up to 10000 basis points")]
+    InvalidFeeSplit,
+    #[msg("No protocol fees to withdraw")]
+    NoProtocolFeesToWithdraw,
+    #[msg("Market fees can only be lowered")]
+    FeeIncreaseNotAllowed,
+    #[msg("Market vault cannot cover its obligations")]
+    Insolvent,
+    #[msg("Liquidity amount is invalid")]
+    InvalidLiquidityAmount,
+    #[msg("Liquidity growth must be at most 10000 bps with a cap no lower than b")]
+    InvalidLiquidityPolicy,
+    #[msg("Purchase exceeds the per-user position limit")]
+    PositionLimitExceeded,
+    #[msg("Purchase exceeds the outcome's total share cap")]
+    OutcomeShareCapExceeded,
+    #[msg("Wallet is not approved for this market")]
+    AccessDenied,
+    #[msg("Permit is missing, malformed or expired")]
+    InvalidPermit,
+    #[msg("Referrer cannot be the trader")]
+    SelfReferral,
+    #[msg("Committee markets need the resolver committee as their oracle")]
+    CommitteeMismatch,
+    #[msg("Permit was already used or revoked")]
+    PermitAlreadyUsed,
   
 
 }
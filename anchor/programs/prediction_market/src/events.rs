@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a market's trading window is moved
+#[event]
+pub struct EndTimeUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub old_end_timestamp: i64,
+    pub new_end_timestamp: i64,
+    pub updated_by: Pubkey,
+}
+
+/// Emitted when a market is paused or unpaused
+#[event]
+pub struct MarketPauseUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub paused: bool,
+    pub updated_by: Pubkey,
+}
+
+/// Emitted when a market lowers its trading fee
+#[event]
+pub struct FeeUpdated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub old_fee_percent: u64,
+    pub new_fee_percent: u64,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::access::Allowance;
+use crate::state::market::Market;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<GrantAllowance>, user: Pubkey) -> Result<()> {
+    let allowance = &mut ctx.accounts.allowance;
+
+    allowance.market = ctx.accounts.market.key();
+    allowance.user = user;
+    allowance.bump = ctx.bumps.allowance;
+
+    msg!("User {} approved for market {}", user, ctx.accounts.market.market_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GrantAllowance<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [b"allowance", market.key().as_ref(), user.as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + Allowance::INIT_SPACE,
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == market.access_authority @ CustomError::Unauthorized
+    )]
+    pub authority: Signer<'info>, // Only the market's access authority can approve wallets
+
+    pub system_program: Program<'info, System>,
+}
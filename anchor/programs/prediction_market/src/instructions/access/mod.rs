@@ -0,0 +1,9 @@
+// Access control for gated markets: the access authority approves wallets
+// with an `Allowance` account or signs short-lived, single-use ed25519 permits.
+pub mod grant;
+pub mod revoke;
+
+pub use grant::GrantAllowance;
+pub use grant::*;
+pub use revoke::RevokeAllowance;
+pub use revoke::*;
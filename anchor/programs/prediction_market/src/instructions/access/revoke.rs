@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::access::{Allowance, PermitNonce};
+use crate::state::market::Market;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<RevokeAllowance>) -> Result<()> {
+    // Skip the user's current nonce so permits already handed out stop working
+    let permit_nonce = &mut ctx.accounts.permit_nonce;
+    permit_nonce.market = ctx.accounts.market.key();
+    permit_nonce.user = ctx.accounts.allowance.user;
+    permit_nonce.nonce = permit_nonce.nonce.checked_add(1).ok_or(CustomError::Overflow)?;
+
+    msg!(
+        "User {} no longer approved for market {}",
+        ctx.accounts.allowance.user,
+        ctx.accounts.market.market_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeAllowance<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"allowance", market.key().as_ref(), allowance.user.as_ref()],
+        bump = allowance.bump,
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"permit", market.key().as_ref(), allowance.user.as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + PermitNonce::INIT_SPACE,
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == market.access_authority @ CustomError::Unauthorized
+    )]
+    pub authority: Signer<'info>, // Only the market's access authority can revoke approvals
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,27 +1,26 @@
-use crate::constants::{SHARES_DECIMALS, TOKEN_DECIMALS};
 use crate::error::CustomError;
+use crate::permit::check_access;
+use crate::state::access::{Allowance, PermitNonce};
+use crate::state::config::Config;
 use crate::state::market::Market;
-use crate::utils::{calculate_cost, calculate_fee};
+use crate::state::position::Position;
+use crate::state::referrer::ReferrerStats;
+use crate::utils::{assert_solvent, calculate_fee};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, MintTo, Token, TokenAccount, Transfer};
 use anchor_spl::token::Mint;
 
-pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) -> Result<()> {
+pub(crate) fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) -> Result<()> {
     let market = &mut ctx.accounts.market;
-
-   
-
     let outcome_mint = &ctx.accounts.outcome_mint;
-     msg!("outcome_mint.mint_authority: {:?}", outcome_mint.mint_authority.unwrap());
     let buyer_share_account = &ctx.accounts.buyer_share_account;
-     msg!("outcome_mint.mint_authority: {:?}", outcome_mint.mint_authority.unwrap());
-    
+
     require!(
-        outcome_mint.mint_authority.unwrap() == market.key(),
+        outcome_mint.mint_authority == COption::Some(market.key()),
         CustomError::InvalidMintAuthority
     );
-  
     require!(
         buyer_share_account.mint == outcome_mint.key(),
         CustomError::InvalidMint
@@ -30,7 +29,19 @@ pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) ->
         buyer_share_account.owner == ctx.accounts.buyer.key(),
         CustomError::InvalidOwner
     );
-    require!(!market.market_closed, CustomError::MarketClosed);
+    require!(!ctx.accounts.config.paused, CustomError::ProtocolPaused);
+    require!(!market.paused, CustomError::MarketPaused);
+    let current_time = Clock::get()?.unix_timestamp;
+    check_access(
+        market,
+        &ctx.accounts.buyer.key(),
+        ctx.accounts.allowance.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref().map(|ix| ix.as_ref()),
+        ctx.accounts.permit_nonce.as_mut(),
+        current_time,
+    )?;
+    require!(current_time >= market.start_timestamp, CustomError::MarketNotOpen);
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
     require!(
         outcome_index < market.outcomes.len() as u64,
         CustomError::InvalidOutcome
@@ -38,9 +49,27 @@ pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) ->
     require!(num_shares > 0, CustomError::InvalidShares);
 
 
-    // Calculate cost before purchase
-    let q_before: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
-    let cost_before = calculate_cost(&q_before, market.b)?;
+    // Price the purchase on the market's curve before updating shares
+    let q_before: Vec<u64> = market.outcome_shares();
+    let cost = market
+        .pricing_curve()
+        .buy_cost(&q_before, outcome_index as usize, num_shares)?;
+
+    // Position limits count every share bought, wherever it was moved since
+    let position = &mut ctx.accounts.position;
+    position.num_shares = position
+        .num_shares
+        .checked_add(num_shares)
+        .ok_or(CustomError::Overflow)?;
+    require!(
+        market.max_shares_per_user == 0 || position.num_shares <= market.max_shares_per_user,
+        CustomError::PositionLimitExceeded
+    );
+    position.market = market.key();
+    position.user = ctx.accounts.buyer.key();
+    position.outcome_index = outcome_index;
+    position.bump = ctx.bumps.position;
+    let max_total_shares = market.max_total_shares_per_outcome;
 
     let outcome = &mut market.outcomes[outcome_index as usize];
     // Update shares
@@ -48,28 +77,30 @@ pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) ->
         .total_shares
         .checked_add(num_shares)
         .ok_or(CustomError::Overflow)?;
-
-    // Calculate cost after purchase
-    let q_after: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
-    let cost_after = calculate_cost(&q_after, market.b)?;
-
-    // Cost difference
-    let cost = cost_after
-        .checked_sub(cost_before)
-        .ok_or(CustomError::MathError)?;
+    require!(
+        max_total_shares == 0 || outcome.total_shares <= max_total_shares,
+        CustomError::OutcomeShareCapExceeded
+    );
 
     // Scale cost difference
     //let cost: u64 = (cost_difference as u128 * 10u128.pow(TOKEN_DECIMALS)) as u64; // Adjust scaling as needed
 
     // Calculate fee
     let fee_amount: u64 = calculate_fee(cost, market.fee_percent)?;
-    let reinvest_amount: u64 = fee_amount / 2;
-    let fee_recipient_amount: u64 = fee_amount - reinvest_amount;
+    let referral_amount: u64 = if ctx.accounts.referrer_stats.is_some() {
+        market.referral_fee(fee_amount)?
+    } else {
+        0
+    };
+    let (reinvest_amount, fee_recipient_amount, protocol_fee_amount) =
+        market.split_fee(fee_amount - referral_amount)?;
     let net_cost: u64 = cost.checked_add(fee_amount).ok_or(CustomError::Overflow)?;
 
     msg!("Fee Amount: {}", fee_amount);
     msg!("Reinvest Amount: {}", reinvest_amount);
     msg!("Fee Recipient Amount: {}", fee_recipient_amount);
+    msg!("Protocol Fee Amount: {}", protocol_fee_amount);
+    msg!("Referral Fee Amount: {}", referral_amount);
     msg!("Gross Cost: {}", cost);
     msg!("Total Cost (Cost + Fee): {}", net_cost);
 
@@ -114,11 +145,36 @@ pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) ->
     market.market_maker_funds = market
         .market_maker_funds
         .checked_add(cost)
+        .and_then(|funds| funds.checked_add(reinvest_amount))
         .ok_or(CustomError::Overflow)?;
     market.collected_fees = market
         .collected_fees
         .checked_add(fee_recipient_amount)
         .ok_or(CustomError::Overflow)?;
+    market.protocol_fees = market
+        .protocol_fees
+        .checked_add(protocol_fee_amount)
+        .ok_or(CustomError::Overflow)?;
+    if let Some(referrer_stats) = ctx.accounts.referrer_stats.as_mut() {
+        market.referral_fees = market
+            .referral_fees
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+        referrer_stats.accrued_fees = referrer_stats
+            .accrued_fees
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+        referrer_stats.total_earned = referrer_stats
+            .total_earned
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+    }
+
+    market.adapt_liquidity(reinvest_amount)?;
+
+    market
+        .pricing_curve()
+        .check_invariant(&market.outcome_shares(), market.market_maker_funds)?;
 
     // Emit event (if using events in Solana; otherwise, use logs)
     msg!(
@@ -127,6 +183,9 @@ pub fn handler(ctx: Context<BuyShares>, outcome_index: u64, num_shares: u64) ->
         outcome_index
     );
 
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
     Ok(())
 }
 
@@ -136,6 +195,35 @@ pub struct BuyShares<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Approval on gated markets, not needed with a permit
+    #[account(seeds = [b"allowance", market.key().as_ref(), buyer.key().as_ref()], bump = allowance.bump)]
+    pub allowance: Option<Account<'info, Allowance>>,
+
+    /// CHECK: instructions sysvar, read for an ed25519 permit on gated markets
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Nonce consumed by a permit, needed only when trading on one
+    #[account(
+        init_if_needed,
+        seeds = [b"permit", market.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        payer = buyer,
+        space = 8 + PermitNonce::INIT_SPACE,
+    )]
+    pub permit_nonce: Option<Account<'info, PermitNonce>>,
+
+    /// Referrer credited with part of the fee
+    #[account(
+        mut,
+        constraint = referrer_stats.market == market.key() @ CustomError::InvalidAccounts,
+        constraint = referrer_stats.referrer != buyer.key() @ CustomError::SelfReferral
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
     #[account(
         init_if_needed,
         payer = buyer,
@@ -163,20 +251,25 @@ pub struct BuyShares<'info> {
 
        #[account(
         mut,
-        //constraint = outcome_mint.key() == market.outcomes[outcome_index as usize].mint,
-        // address = market.outcomes[outcome_index as usize].mint
+        constraint = market.outcomes.get(outcome_index as usize).map(|o| o.mint) == Some(outcome_mint.key()) @ CustomError::InvalidOutcome
     )]
     pub outcome_mint: Account<'info, Mint>,
     
     #[account(
-       mut,
-         //init_if_needed,
-         //payer = buyer,
-         //associated_token::mint = outcome_mint,
-         //associated_token::authority = buyer
-     )]
+        mut,
+        associated_token::mint = outcome_mint,
+        associated_token::authority = buyer
+    )]
     pub buyer_share_account: Account<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"position", market.key().as_ref(), buyer.key().as_ref(), &outcome_index.to_le_bytes()],
+        bump,
+        payer = buyer,
+        space = 8 + Position::INIT_SPACE,
+    )]
+    pub position: Account<'info, Position>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -2,38 +2,48 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Token, TokenAccount, Transfer};
 use anchor_spl::token:: Mint;
 use crate::state::market::Market;
-use crate::constants::PAYOUT_PER_SHARE;
+use crate::utils::assert_solvent;
 use crate::error::CustomError;
 use crate::constants::SHARES_DECIMALS;
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<ClaimPayout>,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let user_share_account = &ctx.accounts.user_share_account;
-    let winning_outcome = market.winning_outcome;
-    
+
+    // Proposals become final once the challenge period passes undisputed
+    market.finalize_if_ready(Clock::get()?.unix_timestamp);
+
     // Validations
+    require!(!market.paused, CustomError::MarketPaused);
     require!(market.market_settled, CustomError::MarketNotSettled);
-    require!(winning_outcome < market.outcomes.len() as u64, CustomError::InvalidOutcome);
+    require!(
+        user_share_account.mint == ctx.accounts.outcome_mint.key(),
+        CustomError::InvalidMint
+    );
 
-    let outcome = &mut market.outcomes[winning_outcome as usize];
+    // Resolve which outcome the shares belong to
+    let outcome_index = market.outcome_index(&ctx.accounts.outcome_mint.key())?;
+    let payout_per_share = market.payout_per_share(outcome_index)?;
     let user_shares = user_share_account.amount;
 
     require!(user_shares > 0, CustomError::NoSharesToClaim);
 
-    let adjusted_shares = user_shares
-    .checked_div(10u64.pow(SHARES_DECIMALS as u32))
-    .ok_or(CustomError::MathError)?;
-
     // Calculate payout
-    let payout = adjusted_shares.checked_mul(PAYOUT_PER_SHARE).ok_or(CustomError::Overflow)?;
+    let payout = user_shares
+        .checked_mul(payout_per_share)
+        .ok_or(CustomError::Overflow)?
+        .checked_div(10u64.pow(SHARES_DECIMALS))
+        .ok_or(CustomError::MathError)?;
 
     // Ensure market has sufficient funds
     require!(market.market_maker_funds >= payout, CustomError::InsufficientFunds);
 
-    // Update market funds
+    // Update market funds and outstanding shares
     market.market_maker_funds = market.market_maker_funds.checked_sub(payout).ok_or(CustomError::Underflow)?;
+    let outcome = &mut market.outcomes[outcome_index];
+    outcome.total_shares = outcome.total_shares.checked_sub(user_shares).ok_or(CustomError::Underflow)?;
 
     // Burn user's shares
     let cpi_accounts = Burn {
@@ -50,23 +60,32 @@ pub fn handler(
     let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
     let signer_seeds = &[&seeds[..]];
 
-    let refund_transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.market_token_account.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: market.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(refund_transfer_ctx, payout)?;
-    msg!(
-        "Transferred {} tokens from market to user's account",
-        payout
-    );
+    // Losing shares are burned without a transfer
+    if payout > 0 {
+        let refund_transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(refund_transfer_ctx, payout)?;
+        msg!(
+            "Transferred {} tokens from market to user's account",
+            payout
+        );
+    }
 
     // Emit event
-    msg!("Payout Claimed: {} tokens to user", payout);
+    if market.market_invalid {
+        msg!("Market was invalidated, shares refunded at last price");
+    }
+    msg!("Payout Claimed: {} tokens to user for outcome {}", payout, outcome_index);
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
 
     Ok(())
 }
@@ -76,7 +95,11 @@ pub struct ClaimPayout<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
     pub market_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
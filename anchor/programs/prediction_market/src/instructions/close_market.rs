@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::market::Market;
 use crate::error::CustomError;
 
-pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<CloseMarket>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let current_time = Clock::get()?.unix_timestamp;
 
@@ -25,12 +25,8 @@ pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
 #[derive(Accounts)]
 pub struct CloseMarket<'info> {
     #[account(mut)]
-    pub market: Account<'info, Market>, // Ensure the oracle is correct
-    
-    #[account(
-        signer, // Ensure this account is signed
-        //address = market.oracle // Ensure this is the oracle account defined in the market
-    )]
-    pub oracle: Signer<'info>, // The oracle signs the transaction 
-    pub system_program: Program<'info, System>,   
+    pub market: Account<'info, Market>,
+
+    pub keeper: Signer<'info>, // Anyone can close the market once it has expired
+    pub system_program: Program<'info, System>,
 }
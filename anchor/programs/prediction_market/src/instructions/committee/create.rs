@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::resolver_committee::ResolverCommittee;
+use crate::error::CustomError;
+
+pub(crate) fn handler(
+    ctx: Context<CreateCommittee>,
+    committee_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let committee = &mut ctx.accounts.committee;
+
+    // Validations
+    let mut unique_members = members.clone();
+    unique_members.sort();
+    unique_members.dedup();
+    require!(
+        !members.is_empty() && unique_members.len() == members.len(),
+        CustomError::InvalidCommittee
+    );
+    require!(
+        threshold > 0 && (threshold as usize) <= members.len(),
+        CustomError::InvalidCommittee
+    );
+
+    committee.authority = ctx.accounts.authority.key();
+    committee.committee_id = committee_id;
+    committee.members = members;
+    committee.threshold = threshold;
+    committee.bump = ctx.bumps.committee;
+
+    msg!(
+        "Committee {} created with {} members and threshold {}",
+        committee.key(),
+        committee.members.len(),
+        threshold
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct CreateCommittee<'info> {
+    #[account(
+        init,
+        seeds = [b"committee", authority.key().as_ref(), committee_id.to_le_bytes().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + ResolverCommittee::INIT_SPACE,
+    )]
+    pub committee: Account<'info, ResolverCommittee>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,9 @@
+// Committee resolution: N members vote on the winning outcome, or to invalidate
+// the market, and the market settles once M of them agree.
+pub mod create;
+pub mod vote;
+
+pub use create::CreateCommittee;
+pub use create::*;
+pub use vote::SubmitVote;
+pub use vote::*;
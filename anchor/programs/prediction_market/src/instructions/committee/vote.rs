@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::market::{Market, ResolutionMode};
+use crate::state::resolver_committee::{ResolutionVote, ResolverCommittee};
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<SubmitVote>, outcome_index: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let committee = &ctx.accounts.committee;
+    let vote = &mut ctx.accounts.vote;
+    let member = ctx.accounts.member.key();
+
+    // Validations
+    require!(
+        market.resolution_mode == ResolutionMode::Committee,
+        CustomError::InvalidResolutionMode
+    );
+    require!(!market.market_settled, CustomError::MarketAlreadySettled);
+    require!(
+        committee.members.contains(&member),
+        CustomError::NotCommitteeMember
+    );
+    require!(
+        (outcome_index as usize) <= market.outcomes.len(),
+        CustomError::InvalidOutcome
+    );
+    // The index after the last outcome votes to invalidate, which a cancelled event
+    // needs while trading is still open
+    let invalidate = outcome_index as usize == market.outcomes.len();
+    require!(invalidate || market.market_closed, CustomError::MarketNotClosed);
+
+    // A member changing their vote moves it to the new outcome
+    if vote.has_voted {
+        let previous = &mut market.committee_votes[vote.outcome_index as usize];
+        *previous = previous.checked_sub(1).ok_or(CustomError::Underflow)?;
+        msg!(
+            "Member {} changed vote from {} to {}",
+            member,
+            vote.outcome_index,
+            outcome_index
+        );
+    }
+    let tally = &mut market.committee_votes[outcome_index as usize];
+    *tally = tally.checked_add(1).ok_or(CustomError::Overflow)?;
+    let tally = *tally;
+
+    vote.market = market.key();
+    vote.member = member;
+    vote.outcome_index = outcome_index;
+    vote.has_voted = true;
+    vote.bump = ctx.bumps.vote;
+
+    msg!(
+        "Committee vote for outcome {} on market {}: {}/{}",
+        outcome_index,
+        market.market_id,
+        tally,
+        committee.threshold
+    );
+
+    if tally >= committee.threshold && invalidate {
+        // Refund every outcome at its last price
+        market.market_closed = true;
+        market.market_invalid = true;
+        let payout_numerators = market.last_prices()?;
+        market.settle(payout_numerators)?;
+        msg!(
+            "Market {} invalidated by committee, refunding at prices {:?} / {}",
+            market.market_id,
+            market.payout_numerators,
+            market.payout_denominator
+        );
+    } else if tally >= committee.threshold {
+        market.winning_outcome = outcome_index;
+        let payout_numerators = (0..market.outcomes.len() as u64)
+            .map(|i| u64::from(i == outcome_index))
+            .collect();
+        market.settle(payout_numerators)?;
+        msg!(
+            "Market {} settled by committee with winning outcome {}",
+            market.market_id,
+            outcome_index
+        );
+    } else {
+        // Flag a split committee so members know they need to reconcile
+        let votes_cast: u64 = market.committee_votes.iter().map(|&v| v as u64).sum();
+        let votes_left = (committee.members.len() as u64).saturating_sub(votes_cast);
+        let leading = market.committee_votes.iter().copied().max().unwrap_or(0) as u64;
+        if leading + votes_left < committee.threshold as u64 {
+            msg!(
+                "Committee deadlocked on market {}, members must change votes to reach {}",
+                market.market_id,
+                committee.threshold
+            );
+        }
+    }
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitVote<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = market.oracle @ CustomError::Unauthorized)]
+    pub committee: Account<'info, ResolverCommittee>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"vote", market.key().as_ref(), member.key().as_ref()],
+        bump,
+        payer = member,
+        space = 8 + ResolutionVote::INIT_SPACE,
+    )]
+    pub vote: Account<'info, ResolutionVote>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::config::Config;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<AddAllowedMint>, mint: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(!config.is_mint_allowed(&mint), CustomError::InvalidAllowedMints);
+    require!(
+        config.allowed_mints.len() < 10,
+        CustomError::InvalidAllowedMints
+    );
+
+    config.allowed_mints.push(mint);
+    msg!("Base mint {} allowed", mint);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddAllowedMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ CustomError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_FEE_BPS;
+use crate::price_feed::PYTH_PROGRAM_ID;
+use crate::state::config::Config;
+use crate::error::CustomError;
+use crate::program::PredictionMarket;
+
+pub(crate) fn handler(
+    ctx: Context<InitializeConfig>,
+    protocol_fee_bps: u64,
+    treasury: Pubkey,
+    max_fee_bps: u64,
+    allowed_mints: Vec<Pubkey>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Validations
+    require!(
+        max_fee_bps <= MAX_FEE_BPS && protocol_fee_bps <= MAX_FEE_BPS,
+        CustomError::FeeTooHigh
+    );
+    require!(allowed_mints.len() <= 10, CustomError::InvalidAllowedMints);
+    let mut unique_mints = allowed_mints.clone();
+    unique_mints.sort();
+    unique_mints.dedup();
+    require!(
+        unique_mints.len() == allowed_mints.len(),
+        CustomError::InvalidAllowedMints
+    );
+
+    config.admin = ctx.accounts.admin.key();
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.treasury = treasury;
+    config.allowed_mints = allowed_mints;
+    config.max_fee_bps = max_fee_bps;
+    config.paused = false;
+    config.price_feed_program = PYTH_PROGRAM_ID;
+    config.bump = ctx.bumps.config;
+
+    msg!(
+        "Config initialized with admin {}, protocol fee {} bps, max fee {} bps",
+        config.admin,
+        protocol_fee_bps,
+        max_fee_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        seeds = [b"config"],
+        bump,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ CustomError::Unauthorized
+    )]
+    pub program: Program<'info, PredictionMarket>,
+
+    // Only the upgrade authority can claim the protocol admin role after a deploy
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,15 @@
+// Protocol configuration: a singleton account holding the admin, protocol fee,
+// treasury and allow-listed base mints.
+pub mod initialize;
+pub mod update;
+pub mod add_allowed_mint;
+pub mod remove_allowed_mint;
+
+pub use initialize::InitializeConfig;
+pub use initialize::*;
+pub use update::UpdateConfig;
+pub use update::*;
+pub use add_allowed_mint::AddAllowedMint;
+pub use add_allowed_mint::*;
+pub use remove_allowed_mint::RemoveAllowedMint;
+pub use remove_allowed_mint::*;
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::config::Config;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<RemoveAllowedMint>, mint: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Existing markets keep trading, only new markets are affected
+    require!(config.is_mint_allowed(&mint), CustomError::MintNotAllowed);
+    config.allowed_mints.retain(|m| *m != mint);
+    msg!("Base mint {} removed from the allow list", mint);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ CustomError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_FEE_BPS;
+use crate::state::config::Config;
+use crate::error::CustomError;
+
+pub(crate) fn handler(
+    ctx: Context<UpdateConfig>,
+    new_admin: Option<Pubkey>,
+    protocol_fee_bps: Option<u64>,
+    treasury: Option<Pubkey>,
+    max_fee_bps: Option<u64>,
+    paused: Option<bool>,
+    price_feed_program: Option<Pubkey>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(new_admin) = new_admin {
+        config.admin = new_admin;
+    }
+    if let Some(protocol_fee_bps) = protocol_fee_bps {
+        config.protocol_fee_bps = protocol_fee_bps;
+    }
+    if let Some(treasury) = treasury {
+        config.treasury = treasury;
+    }
+    if let Some(max_fee_bps) = max_fee_bps {
+        config.max_fee_bps = max_fee_bps;
+    }
+    if let Some(paused) = paused {
+        config.paused = paused;
+    }
+    if let Some(price_feed_program) = price_feed_program {
+        config.price_feed_program = price_feed_program;
+    }
+
+    require!(
+        config.max_fee_bps <= MAX_FEE_BPS && config.protocol_fee_bps <= MAX_FEE_BPS,
+        CustomError::FeeTooHigh
+    );
+
+    msg!(
+        "Config updated: admin {}, protocol fee {} bps, treasury {}, max fee {} bps, paused {}, price feed program {}",
+        config.admin,
+        config.protocol_fee_bps,
+        config.treasury,
+        config.max_fee_bps,
+        config.paused,
+        config.price_feed_program
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ CustomError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -1,35 +1,143 @@
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::{self, spl_token,InitializeMint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, spl_token,InitializeMint, MintTo, Token, TokenAccount, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
 
 //use anchor_spl::token_interface::Mint;
-use crate::constants::SHARES_DECIMALS;
+use crate::constants::{MAX_FEE_BPS, SCALE, SHARES_DECIMALS, TOKEN_DECIMALS};
+use crate::utils::assert_solvent;
 use crate::error::CustomError;
-use crate::state::market::Market;
+use crate::state::config::Config;
+use crate::state::market::{CurveType, Market, MarketType, PriceFeedConfig, ResolutionMode};
 use crate::state::outcome::Outcome;
+use crate::state::resolver_committee::ResolverCommittee;
+use crate::curves::pricing_curve;
 use anchor_spl::token::Mint;
 use solana_program::program_pack::Pack;
 
-pub fn handler<'info>(
+pub(crate) fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CreateMarket<'info>>,
     market_id: u64,
     title: String,
     outcomes: Vec<String>,
     oracle: Pubkey,
     b: u64,
-    duration: i64,
+    start_timestamp: i64,
+    end_timestamp: i64,
     fee_percent: u64,
     fee_recipient: Pubkey,
     initial_funds: u64,
+    curve: CurveType,
+    alpha: u64,
+    market_type: MarketType,
+    lower_bound: i64,
+    upper_bound: i64,
+    challenge_period: i64,
+    arbitrator: Pubkey,
+    dispute_bond: u64,
+    resolution_mode: ResolutionMode,
+    price_feed: PriceFeedConfig,
+    lp_fee_bps: u64,
+    creator_fee_bps: u64,
+    liquidity_growth_bps: u64,
+    max_b: u64,
+    max_shares_per_user: u64,
+    max_total_shares_per_outcome: u64,
+    access_authority: Pubkey,
+    referral_fee_bps: u64,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let base_token_mint_info = ctx.accounts.base_token_mint.to_account_info();
     let clock = Clock::get()?;
 
-    require!(outcomes.len() > 0, CustomError::NoOutcomes);
+    let config = &ctx.accounts.config;
+    require!(!config.paused, CustomError::ProtocolPaused);
+    require!(
+        config.is_mint_allowed(&ctx.accounts.base_token_mint.key()),
+        CustomError::MintNotAllowed
+    );
+    require!(fee_percent <= config.max_fee_bps, CustomError::FeeTooHigh);
+    require!(referral_fee_bps <= MAX_FEE_BPS, CustomError::InvalidFeeSplit);
+    let protocol_fee_bps = config.protocol_fee_bps;
+    require!(
+        lp_fee_bps
+            .checked_add(creator_fee_bps)
+            .and_then(|bps| bps.checked_add(protocol_fee_bps))
+            == Some(MAX_FEE_BPS),
+        CustomError::InvalidFeeSplit
+    );
+
+    require!(!outcomes.is_empty(), CustomError::NoOutcomes);
     require!(b > 0, CustomError::InvalidB);
-    require!(duration > 0, CustomError::InvalidDuration);
+    require!(
+        start_timestamp < end_timestamp && end_timestamp > clock.unix_timestamp,
+        CustomError::InvalidDuration
+    );
+    if curve == CurveType::LsLmsr {
+        require!(alpha > 0 && alpha < SCALE, CustomError::InvalidAlpha);
+    }
+    if curve == CurveType::Cpmm {
+        require!(outcomes.len() == 2, CustomError::InvalidCurveOutcomes);
+    }
+    require!(
+        challenge_period >= 0 && (challenge_period == 0 || dispute_bond > 0),
+        CustomError::InvalidDisputeConfig
+    );
+    // Disputed proposals only settle through the arbitrator
+    require!(
+        challenge_period == 0 || arbitrator != Pubkey::default(),
+        CustomError::InvalidDisputeConfig
+    );
+    if resolution_mode == ResolutionMode::Optimistic {
+        // Proposals need a liveness period, a bond to be worth disputing and an arbitrator
+        // to rule on disputes
+        require!(
+            challenge_period > 0 && dispute_bond > 0 && arbitrator != Pubkey::default(),
+            CustomError::InvalidDisputeConfig
+        );
+    }
+    if resolution_mode == ResolutionMode::Committee {
+        // Committees vote on a single winning outcome
+        require!(
+            market_type == MarketType::Categorical,
+            CustomError::InvalidMarketType
+        );
+        // Only an existing committee can ever settle or invalidate the market
+        let committee = ctx
+            .accounts
+            .committee
+            .as_ref()
+            .ok_or(CustomError::CommitteeMismatch)?;
+        require_keys_eq!(committee.key(), oracle, CustomError::CommitteeMismatch);
+    }
+    if resolution_mode == ResolutionMode::PriceFeed {
+        // Categorical feed markets are binary: the condition holds or it doesn't
+        require!(
+            price_feed.feed != Pubkey::default()
+                && price_feed.max_staleness > 0
+                && (market_type == MarketType::Scalar || outcomes.len() == 2),
+            CustomError::InvalidPriceFeedConfig
+        );
+    }
+    if liquidity_growth_bps > 0 {
+        require!(
+            liquidity_growth_bps <= MAX_FEE_BPS && max_b >= b,
+            CustomError::InvalidLiquidityPolicy
+        );
+    }
+    if market_type == MarketType::Scalar {
+        require!(
+            outcomes.len() == 2 && lower_bound < upper_bound,
+            CustomError::InvalidScalarBounds
+        );
+    }
+
+    // The market maker must be able to cover its worst-case loss from day one
+    let required_funds = pricing_curve(curve, b, alpha).max_loss(outcomes.len())?;
+    require!(
+        initial_funds >= required_funds,
+        CustomError::InsufficientInitialFunds
+    );
 
     require!(
         base_token_mint_info.data_len() == spl_token::state::Mint::LEN,
@@ -49,7 +157,22 @@ pub fn handler<'info>(
     market.b = b;
     market.fee_percent = fee_percent;
     market.fee_recipient = fee_recipient;
-    market.end_timestamp = clock.unix_timestamp + duration;
+    market.start_timestamp = start_timestamp;
+    market.end_timestamp = end_timestamp;
+    market.authority = ctx.accounts.user.key();
+    market.paused = false;
+    market.lp_fee_bps = lp_fee_bps;
+    market.creator_fee_bps = creator_fee_bps;
+    market.protocol_fee_bps = protocol_fee_bps;
+    market.protocol_fees = 0;
+    market.lp_mint_bump = ctx.bumps.lp_mint;
+    market.liquidity_growth_bps = liquidity_growth_bps;
+    market.max_b = if liquidity_growth_bps > 0 { max_b } else { b };
+    market.max_shares_per_user = max_shares_per_user;
+    market.max_total_shares_per_outcome = max_total_shares_per_outcome;
+    market.access_authority = access_authority;
+    market.referral_fee_bps = referral_fee_bps;
+    market.referral_fees = 0;
     market.market_closed = false;
     market.market_settled = false;
     market.winning_outcome = 0;
@@ -57,6 +180,30 @@ pub fn handler<'info>(
     market.initial_funds = initial_funds;
     market.collected_fees = 0;
     market.base_token_mint = ctx.accounts.base_token_mint.key();
+    market.curve_type = curve;
+    market.alpha = if curve == CurveType::LsLmsr { alpha } else { 0 };
+    market.market_type = market_type;
+    market.lower_bound = lower_bound;
+    market.upper_bound = upper_bound;
+    market.resolved_value = 0;
+    market.payout_numerators = vec![];
+    market.payout_denominator = 0;
+    market.market_invalid = false;
+    market.challenge_period = challenge_period;
+    market.arbitrator = arbitrator;
+    market.dispute_bond = dispute_bond;
+    market.outcome_proposed = false;
+    market.outcome_proposed_at = 0;
+    market.outcome_disputed = false;
+    market.disputer = Pubkey::default();
+    market.resolution_mode = resolution_mode;
+    market.price_feed = price_feed;
+    // One tally per outcome plus a last one for invalidating the market
+    market.committee_votes = if resolution_mode == ResolutionMode::Committee {
+        vec![0; outcomes.len() + 1]
+    } else {
+        vec![]
+    };
     //market.outcomes = vec![]; // Initialize outcomes
 
     // Dynamically process remaining accounts as outcome mints
@@ -114,6 +261,7 @@ pub fn handler<'info>(
         market.outcomes.push(Outcome {
             name: outcome_name.clone(),
             total_shares: 0,
+            virtual_shares: 0,
             mint: outcome_mint.key(),
         });
     }
@@ -129,11 +277,32 @@ pub fn handler<'info>(
 
     msg!("Transferred {} initial funds to market's token account", initial_funds);
 
+    // The creator is the first liquidity provider, one LP token per base unit
+    let market_id_bytes: [u8; 8] = market_id.to_le_bytes();
+    let seeds = &[b"market", &market_id_bytes[..], &[bump]];
+    let signer_seeds = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        initial_funds,
+    )?;
+    msg!("Minted {} LP tokens to the market creator", initial_funds);
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(market_id: u64, title: String, outcomes: Vec<String>, oracle: Pubkey, b: u64, duration: i64, fee_percent: u64, fee_recipient: Pubkey, initial_funds: u64)]
+#[instruction(market_id: u64, title: String, outcomes: Vec<String>, oracle: Pubkey, b: u64, start_timestamp: i64, end_timestamp: i64, fee_percent: u64, fee_recipient: Pubkey, initial_funds: u64, curve: CurveType, alpha: u64, market_type: MarketType, lower_bound: i64, upper_bound: i64, challenge_period: i64, arbitrator: Pubkey, dispute_bond: u64, resolution_mode: ResolutionMode, price_feed: PriceFeedConfig, lp_fee_bps: u64, creator_fee_bps: u64, liquidity_growth_bps: u64, max_b: u64, max_shares_per_user: u64, max_total_shares_per_outcome: u64, access_authority: Pubkey, referral_fee_bps: u64)]
 pub struct CreateMarket<'info> {
     #[account(
         init,
@@ -144,6 +313,9 @@ pub struct CreateMarket<'info> {
     )]
     pub market: Account<'info, Market>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -166,6 +338,27 @@ pub struct CreateMarket<'info> {
     )]
     pub market_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump,
+        payer = user,
+        mint::decimals = TOKEN_DECIMALS as u8,
+        mint::authority = market
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Resolver committee of a `ResolutionMode::Committee` market, passed as `oracle`
+    pub committee: Option<Box<Account<'info, ResolverCommittee>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user
+    )]
+    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
+
     #[account(address = anchor_lang::solana_program::system_program::ID)]
     pub system_program: Program<'info, System>,
 
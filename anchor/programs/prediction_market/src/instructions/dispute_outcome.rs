@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<DisputeOutcome>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validations
+    require!(market.outcome_proposed, CustomError::OutcomeNotProposed);
+    require!(!market.outcome_disputed, CustomError::OutcomeAlreadyDisputed);
+    require!(
+        market.challenge_window_open(current_time),
+        CustomError::ChallengePeriodEnded
+    );
+
+    // Post the dispute bond into the market's token account
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.disputer_token_account.to_account_info(),
+        to: ctx.accounts.market_token_account.to_account_info(),
+        authority: ctx.accounts.disputer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token::transfer(cpi_ctx, market.dispute_bond)?;
+
+    market.outcome_disputed = true;
+    market.disputer = ctx.accounts.disputer.key();
+
+    msg!(
+        "Outcome of market {} disputed by {} with bond {}, escalated to arbitrator {}",
+        market.market_id,
+        market.disputer,
+        market.dispute_bond,
+        market.arbitrator
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = disputer
+    )]
+    pub disputer_token_account: Account<'info, TokenAccount>,
+
+    pub disputer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
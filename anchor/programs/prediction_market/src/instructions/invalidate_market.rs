@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::market::Market;
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<InvalidateMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // A cancelled event can be invalidated while trading is still open
+    market.market_closed = true;
+    market.market_invalid = true;
+
+    // Refund every outcome at its last price; the prices become the payout numerators
+    let payout_numerators = market.last_prices()?;
+    market.propose_payouts(payout_numerators, current_time)?;
+    msg!(
+        "Market {} proposed invalidation, refunding at prices {:?} / {}",
+        market.market_id,
+        market.payout_numerators,
+        market.payout_denominator
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InvalidateMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = oracle.key() == market.oracle @ CustomError::Unauthorized
+    )]
+    pub oracle: Signer<'info>, // Only the oracle can invalidate the market
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validations
+    require!(!market.paused, CustomError::MarketPaused);
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
+    require!(amount > 0, CustomError::InvalidLiquidityAmount);
+    require!(market.market_maker_funds > 0, CustomError::InsufficientFunds);
+
+    // LP tokens are minted against the equity at current prices, so a new LP
+    // neither pays for nor profits from shares sold before the deposit
+    let equity = market.lp_equity()?;
+    require!(equity > 0, CustomError::InsufficientFunds);
+    let old_funds = market.market_maker_funds;
+    let new_funds = old_funds.checked_add(amount).ok_or(CustomError::Overflow)?;
+    let lp_amount = (ctx.accounts.lp_mint.supply as u128)
+        .checked_mul(amount as u128)
+        .and_then(|a| a.checked_div(equity as u128))
+        .ok_or(CustomError::MathError)?;
+    let lp_amount = u64::try_from(lp_amount).map_err(|_| error!(CustomError::Overflow))?;
+    require!(lp_amount > 0, CustomError::InvalidLiquidityAmount);
+
+    // Deepen the market by the same factor as its funds
+    market.rescale_liquidity(new_funds, old_funds)?;
+    market.market_maker_funds = new_funds;
+    market
+        .pricing_curve()
+        .check_invariant(&market.outcome_shares(), market.market_maker_funds)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.provider_token_account.to_account_info(),
+        to: ctx.accounts.market_token_account.to_account_info(),
+        authority: ctx.accounts.provider.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let market_id_bytes: [u8; 8] = market.market_id.to_le_bytes();
+    let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
+    let signer_seeds = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.provider_lp_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        lp_amount,
+    )?;
+
+    msg!(
+        "Liquidity Added: {} tokens for {} LP tokens, market {} now has b = {}",
+        amount,
+        lp_amount,
+        market.market_id,
+        market.b
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump = market.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = provider
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::mint = lp_mint,
+        associated_token::authority = provider
+    )]
+    pub provider_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<IncreaseLiquidity>, new_b: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validations
+    require!(!market.paused, CustomError::MarketPaused);
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
+    require!(new_b > market.b, CustomError::InvalidB);
+
+    // Scale q with b so prices stay put; the deposit is the change in the
+    // cost function, C(q'; b') - C(q; b)
+    let old_b = market.b;
+    let equity = market.lp_equity()?;
+    require!(equity > 0, CustomError::InsufficientFunds);
+    let cost_before = market.pricing_curve().cost(&market.outcome_shares())?;
+    market.rescale_liquidity(new_b, old_b)?;
+    let cost_after = market.pricing_curve().cost(&market.outcome_shares())?;
+    let deposit = cost_after.saturating_sub(cost_before);
+
+    // Prices don't move, so the deposit adds exactly its amount to the LP equity
+    // and is paid for in LP tokens at the current equity, as in add_liquidity
+    let lp_amount = (ctx.accounts.lp_mint.supply as u128)
+        .checked_mul(deposit as u128)
+        .and_then(|a| a.checked_div(equity as u128))
+        .ok_or(CustomError::MathError)?;
+    let lp_amount = u64::try_from(lp_amount).map_err(|_| error!(CustomError::Overflow))?;
+    require!(lp_amount > 0, CustomError::InvalidLiquidityAmount);
+
+    market.market_maker_funds = market
+        .market_maker_funds
+        .checked_add(deposit)
+        .ok_or(CustomError::Overflow)?;
+    market
+        .pricing_curve()
+        .check_invariant(&market.outcome_shares(), market.market_maker_funds)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        to: ctx.accounts.market_token_account.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, deposit)?;
+
+    let market_id_bytes: [u8; 8] = market.market_id.to_le_bytes();
+    let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
+    let signer_seeds = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.depositor_lp_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        lp_amount,
+    )?;
+
+    msg!(
+        "Liquidity Increased: market {} b raised from {} to {} for {} tokens and {} LP tokens",
+        market.market_id,
+        old_b,
+        new_b,
+        deposit,
+        lp_amount
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct IncreaseLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump = market.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = lp_mint,
+        associated_token::authority = depositor
+    )]
+    pub depositor_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>, // Anyone can deepen a market and becomes an LP
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,13 @@
+// Liquidity provision: LPs deposit base tokens for LP tokens, deepening the
+// market without moving prices, and share the residual funds at settlement.
+// Anyone can also subsidize a deeper `b` without receiving LP tokens.
+pub mod add;
+pub mod remove;
+pub mod increase;
+
+pub use add::AddLiquidity;
+pub use add::*;
+pub use remove::RemoveLiquidity;
+pub use remove::*;
+pub use increase::IncreaseLiquidity;
+pub use increase::*;
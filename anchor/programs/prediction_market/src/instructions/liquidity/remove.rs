@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<RemoveLiquidity>, lp_amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Liquidity stays locked until the result is final
+    market.finalize_if_ready(Clock::get()?.unix_timestamp);
+    require!(market.market_settled, CustomError::MarketNotSettled);
+    require!(lp_amount > 0, CustomError::InvalidLiquidityAmount);
+
+    // Whatever is left after every outstanding share is paid belongs to the LPs,
+    // including the reinvested LP fees
+    let residual_funds = market
+        .market_maker_funds
+        .checked_sub(market.outstanding_payouts()?)
+        .ok_or(CustomError::InsufficientFunds)?;
+    let withdrawal = (residual_funds as u128)
+        .checked_mul(lp_amount as u128)
+        .and_then(|w| w.checked_div(ctx.accounts.lp_mint.supply as u128))
+        .ok_or(CustomError::MathError)?;
+    let withdrawal = withdrawal as u64;
+
+    market.market_maker_funds = market
+        .market_maker_funds
+        .checked_sub(withdrawal)
+        .ok_or(CustomError::Underflow)?;
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_token_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    if withdrawal > 0 {
+        let market_id_bytes: [u8; 8] = market.market_id.to_le_bytes();
+        let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.market_token_account.to_account_info(),
+                    to: ctx.accounts.provider_token_account.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            withdrawal,
+        )?;
+    }
+
+    msg!(
+        "Liquidity Removed: {} LP tokens for {} tokens from market {}",
+        lp_amount,
+        withdrawal,
+        market.market_id
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump = market.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = provider
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider
+    )]
+    pub provider_lp_token_account: Account<'info, TokenAccount>,
+
+    pub provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
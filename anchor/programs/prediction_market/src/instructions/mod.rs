@@ -1,12 +1,33 @@
 // In src/instructions/mod.rs
+// Handlers are crate-private and called by module path from lib.rs, so the glob
+// re-exports only carry the account structs and their generated client modules.
+pub mod access;
+pub mod config;
 pub mod create_market;
 pub mod buy_shares;
 pub mod sell_shares;
 pub mod close_market;
-pub mod set_outcome;
+pub mod propose_outcome;
+pub mod set_scalar_outcome;
+pub mod report_payouts;
+pub mod invalidate_market;
+pub mod dispute_outcome;
+pub mod resolve_dispute;
+pub mod optimistic_oracle;
+pub mod committee;
+pub mod liquidity;
+pub mod referral;
+pub mod resolve_from_feed;
+pub mod update_end_time;
+pub mod update_fee;
+pub mod pause_market;
+pub mod unpause_market;
 pub mod claim_payout;
 pub mod withdraw_fees;
+pub mod withdraw_protocol_fees;
 
+pub use access::*;
+pub use config::*;
 pub use create_market::CreateMarket;
 pub use create_market::*;
 pub use buy_shares::BuyShares;
@@ -15,10 +36,36 @@ pub use sell_shares::SellShares;
 pub use sell_shares::*;
 pub use close_market::CloseMarket;
 pub use close_market::*;
-pub use set_outcome::SetOutcome;
-pub use set_outcome::*;
+pub use propose_outcome::ProposeOutcome;
+pub use propose_outcome::*;
+pub use set_scalar_outcome::SetScalarOutcome;
+pub use set_scalar_outcome::*;
+pub use report_payouts::ReportPayouts;
+pub use report_payouts::*;
+pub use invalidate_market::InvalidateMarket;
+pub use invalidate_market::*;
+pub use dispute_outcome::DisputeOutcome;
+pub use dispute_outcome::*;
+pub use resolve_dispute::ResolveDispute;
+pub use resolve_dispute::*;
+pub use optimistic_oracle::*;
+pub use committee::*;
+pub use liquidity::*;
+pub use referral::*;
+pub use resolve_from_feed::ResolveFromFeed;
+pub use resolve_from_feed::*;
+pub use update_end_time::UpdateEndTime;
+pub use update_end_time::*;
+pub use update_fee::UpdateFee;
+pub use update_fee::*;
+pub use pause_market::PauseMarket;
+pub use pause_market::*;
+pub use unpause_market::UnpauseMarket;
+pub use unpause_market::*;
 pub use claim_payout::ClaimPayout;
 pub use claim_payout::*;
 pub use withdraw_fees::WithdrawFees;
 pub use withdraw_fees::*;
+pub use withdraw_protocol_fees::WithdrawProtocolFees;
+pub use withdraw_protocol_fees::*;
 
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::state::optimistic_resolution::OptimisticResolution;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<DisputeOptimisticOutcome>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let resolution = &mut ctx.accounts.resolution;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validations
+    require!(!resolution.resolved, CustomError::ResolutionAlreadyFinished);
+    require!(!resolution.disputed, CustomError::OutcomeAlreadyDisputed);
+    require!(
+        current_time < resolution.proposed_at.saturating_add(market.challenge_period),
+        CustomError::ChallengePeriodEnded
+    );
+
+    // Match the proposer bond
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.disputer_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.disputer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token::transfer(cpi_ctx, resolution.bond)?;
+
+    resolution.disputed = true;
+    resolution.disputer = ctx.accounts.disputer.key();
+
+    msg!(
+        "Optimistic outcome for market {} disputed by {}, escalated to arbitrator {}",
+        market.market_id,
+        resolution.disputer,
+        market.arbitrator
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DisputeOptimisticOutcome<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic", market.key().as_ref()],
+        bump = resolution.bump,
+    )]
+    pub resolution: Account<'info, OptimisticResolution>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = resolution
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = disputer
+    )]
+    pub disputer_token_account: Account<'info, TokenAccount>,
+
+    pub disputer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
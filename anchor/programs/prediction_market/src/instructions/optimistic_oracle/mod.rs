@@ -0,0 +1,15 @@
+// Optimistic-oracle resolution: anyone proposes a result by posting a bond,
+// anyone may dispute by matching it, and the market's arbitrator settles disputes.
+pub mod propose;
+pub mod dispute;
+pub mod settle;
+pub mod resolve;
+
+pub use propose::ProposeOptimisticOutcome;
+pub use propose::*;
+pub use dispute::DisputeOptimisticOutcome;
+pub use dispute::*;
+pub use settle::SettleOptimisticOutcome;
+pub use settle::*;
+pub use resolve::ResolveOptimisticDispute;
+pub use resolve::*;
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::market::{Market, ResolutionMode};
+use crate::state::optimistic_resolution::OptimisticResolution;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<ProposeOptimisticOutcome>, payout_numerators: Vec<u64>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let resolution = &mut ctx.accounts.resolution;
+
+    // Validations
+    require!(
+        market.resolution_mode == ResolutionMode::Optimistic,
+        CustomError::InvalidResolutionMode
+    );
+    require!(market.market_closed, CustomError::MarketNotClosed);
+    require!(!market.market_settled, CustomError::MarketAlreadySettled);
+    require!(
+        payout_numerators.len() == market.outcomes.len()
+            && payout_numerators.iter().any(|&n| n > 0),
+        CustomError::InvalidPayouts
+    );
+
+    // Post the proposer bond
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.proposer_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.proposer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token::transfer(cpi_ctx, market.dispute_bond)?;
+
+    resolution.market = market.key();
+    resolution.proposer = ctx.accounts.proposer.key();
+    resolution.payout_numerators = payout_numerators;
+    resolution.proposed_at = Clock::get()?.unix_timestamp;
+    resolution.bond = market.dispute_bond;
+    resolution.disputed = false;
+    resolution.disputer = Pubkey::default();
+    resolution.resolved = false;
+    resolution.bump = ctx.bumps.resolution;
+
+    msg!(
+        "Optimistic outcome {:?} proposed for market {} with bond {}",
+        resolution.payout_numerators,
+        market.market_id,
+        resolution.bond
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeOptimisticOutcome<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [b"optimistic", market.key().as_ref()],
+        bump,
+        payer = proposer,
+        space = 8 + OptimisticResolution::INIT_SPACE,
+    )]
+    pub resolution: Account<'info, OptimisticResolution>,
+
+    #[account(address = market.base_token_mint)]
+    pub base_token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = resolution
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = base_token_mint,
+        token::authority = proposer
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::state::optimistic_resolution::OptimisticResolution;
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<ResolveOptimisticDispute>, payout_numerators: Vec<u64>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolution = &mut ctx.accounts.resolution;
+
+    // Validations
+    require!(!resolution.resolved, CustomError::ResolutionAlreadyFinished);
+    require!(resolution.disputed, CustomError::OutcomeNotDisputed);
+
+    // The arbitrator's ruling settles the market
+    market.settle(payout_numerators)?;
+    resolution.resolved = true;
+
+    // Whoever the ruling agrees with receives both bonds
+    let winner = if market.payouts_match(&resolution.payout_numerators) {
+        resolution.proposer
+    } else {
+        resolution.disputer
+    };
+    require!(
+        ctx.accounts.winner_token_account.owner == winner,
+        CustomError::InvalidOwner
+    );
+    let winnings = resolution.bond.checked_mul(2).ok_or(CustomError::Overflow)?;
+
+    let market_key = market.key();
+    let seeds = &[b"optimistic", market_key.as_ref(), &[resolution.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let payout_transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.winner_token_account.to_account_info(),
+            authority: resolution.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(payout_transfer_ctx, winnings)?;
+
+    msg!(
+        "Market {} settled by arbitrator with payouts {:?} / {}, {} bonds paid to {}",
+        market.market_id,
+        market.payout_numerators,
+        market.payout_denominator,
+        winnings,
+        winner
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveOptimisticDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic", market.key().as_ref()],
+        bump = resolution.bump,
+    )]
+    pub resolution: Account<'info, OptimisticResolution>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = resolution
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = arbitrator.key() == market.arbitrator @ CustomError::Unauthorized
+    )]
+    pub arbitrator: Signer<'info>, // Only the arbitrator can rule on disputes
+
+    pub token_program: Program<'info, Token>,
+}
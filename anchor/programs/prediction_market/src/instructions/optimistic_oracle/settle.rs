@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::state::optimistic_resolution::OptimisticResolution;
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<SettleOptimisticOutcome>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolution = &mut ctx.accounts.resolution;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Validations
+    require!(!resolution.resolved, CustomError::ResolutionAlreadyFinished);
+    require!(!resolution.disputed, CustomError::OutcomeAlreadyDisputed);
+    require!(
+        current_time >= resolution.proposed_at.saturating_add(market.challenge_period),
+        CustomError::ChallengePeriodNotEnded
+    );
+
+    // The undisputed proposal becomes the market's result
+    market.settle(resolution.payout_numerators.clone())?;
+    resolution.resolved = true;
+
+    // Return the proposer bond
+    let market_key = market.key();
+    let seeds = &[b"optimistic", market_key.as_ref(), &[resolution.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let refund_transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.proposer_token_account.to_account_info(),
+            authority: resolution.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(refund_transfer_ctx, resolution.bond)?;
+
+    msg!(
+        "Market {} settled optimistically with payouts {:?} / {}",
+        market.market_id,
+        market.payout_numerators,
+        market.payout_denominator
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleOptimisticOutcome<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic", market.key().as_ref()],
+        bump = resolution.bump,
+    )]
+    pub resolution: Account<'info, OptimisticResolution>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = resolution
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        constraint = proposer_token_account.owner == resolution.proposer @ CustomError::InvalidOwner
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
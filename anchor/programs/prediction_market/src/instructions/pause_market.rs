@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::config::Config;
+use crate::state::market::Market;
+use crate::error::CustomError;
+use crate::events::MarketPauseUpdated;
+
+pub(crate) fn handler(ctx: Context<PauseMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(!market.paused, CustomError::MarketPaused);
+    market.paused = true;
+
+    emit!(MarketPauseUpdated {
+        market: market.key(),
+        market_id: market.market_id,
+        paused: true,
+        updated_by: ctx.accounts.signer.key(),
+    });
+    msg!("Market {} paused", market.market_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PauseMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = signer.key() == market.authority || signer.key() == config.admin @ CustomError::Unauthorized
+    )]
+    pub signer: Signer<'info>, // The market authority or the protocol admin
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::market::{Market, MarketType};
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<ProposeOutcome>, winning_outcome: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Ensure the market is closed before proposing the outcome
+    require!(market.market_closed, CustomError::MarketNotClosed);
+
+    // Scalar markets are resolved with set_scalar_outcome
+    require!(
+        market.market_type == MarketType::Categorical,
+        CustomError::InvalidMarketType
+    );
+
+    // Validate the winning outcome index
+    require!(
+        (winning_outcome as usize) < market.outcomes.len(),
+        CustomError::InvalidOutcome
+    );
+
+    // Record the winning outcome, it becomes final after the challenge period
+    market.winning_outcome = winning_outcome;
+    let payout_numerators = (0..market.outcomes.len() as u64)
+        .map(|i| u64::from(i == winning_outcome))
+        .collect();
+    market.propose_payouts(payout_numerators, current_time)?;
+    msg!(
+        "Market {} proposed winning outcome {}, disputable until {}",
+        market.market_id,
+        winning_outcome,
+        market.outcome_proposed_at + market.challenge_period
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = oracle.key() == market.oracle @ CustomError::Unauthorized
+    )]
+    pub oracle: Signer<'info>,          // Only the oracle can propose the outcome
+}
@@ -0,0 +1,9 @@
+// Referral program: partners register per market and earn part of the trading
+// fee on trades that name their `ReferrerStats` account.
+pub mod register;
+pub mod withdraw;
+
+pub use register::RegisterReferrer;
+pub use register::*;
+pub use withdraw::WithdrawReferralFees;
+pub use withdraw::*;
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::market::Market;
+use crate::state::referrer::ReferrerStats;
+
+pub(crate) fn handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let stats = &mut ctx.accounts.referrer_stats;
+
+    stats.market = ctx.accounts.market.key();
+    stats.referrer = ctx.accounts.referrer.key();
+    stats.accrued_fees = 0;
+    stats.total_earned = 0;
+    stats.bump = ctx.bumps.referrer_stats;
+
+    msg!(
+        "Referrer {} registered for market {}",
+        stats.referrer,
+        ctx.accounts.market.market_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [b"referrer", market.key().as_ref(), referrer.key().as_ref()],
+        bump,
+        payer = referrer,
+        space = 8 + ReferrerStats::INIT_SPACE,
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
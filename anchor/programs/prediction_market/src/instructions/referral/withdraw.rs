@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::state::referrer::ReferrerStats;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<WithdrawReferralFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let stats = &mut ctx.accounts.referrer_stats;
+
+    let fees = stats.accrued_fees;
+    require!(fees > 0, CustomError::NoFeesToWithdraw);
+
+    market.referral_fees = market
+        .referral_fees
+        .checked_sub(fees)
+        .ok_or(CustomError::Underflow)?;
+    stats.accrued_fees = 0;
+
+    let market_id_bytes: [u8; 8] = market.market_id.to_le_bytes();
+    let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_token_account.to_account_info(),
+            to: ctx.accounts.referrer_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, fees)?;
+
+    msg!(
+        "Referral Fees Withdrawn: {} tokens from market {} to referrer {}",
+        fees,
+        market.market_id,
+        stats.referrer
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReferralFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", market.key().as_ref(), referrer.key().as_ref()],
+        bump = referrer_stats.bump,
+        has_one = referrer @ CustomError::Unauthorized
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        token::authority = referrer
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    pub referrer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::market::Market;
+use crate::utils::assert_solvent;
+use crate::error::CustomError;
+
+pub(crate) fn handler(ctx: Context<ResolveDispute>, payout_numerators: Vec<u64>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Validations
+    require!(market.outcome_disputed, CustomError::OutcomeNotDisputed);
+
+    // The disputer is refunded only if the arbitrator overturns the proposal
+    let proposal_upheld = market.payouts_match(&payout_numerators);
+    let bond = market.dispute_bond;
+
+    market.settle(payout_numerators)?;
+
+    if proposal_upheld {
+        // A failed dispute forfeits the bond to the protocol treasury, not to the
+        // fee recipient, who is often the oracle whose proposal was challenged
+        market.protocol_fees = market
+            .protocol_fees
+            .checked_add(bond)
+            .ok_or(CustomError::Overflow)?;
+        msg!("Proposal upheld, dispute bond {} forfeited", bond);
+    } else {
+        market.market_invalid = false;
+
+        let market_id_bytes: [u8; 8] = market.market_id.to_le_bytes();
+        let seeds = &[b"market", &market_id_bytes[..], &[market.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let refund_transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_token_account.to_account_info(),
+                to: ctx.accounts.disputer_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(refund_transfer_ctx, bond)?;
+        msg!("Proposal overturned, dispute bond {} returned to disputer", bond);
+    }
+
+    msg!(
+        "Market {} settled by arbitrator with payouts {:?} / {}",
+        market.market_id,
+        market.payout_numerators,
+        market.payout_denominator
+    );
+
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = market.base_token_mint,
+        constraint = disputer_token_account.owner == market.disputer @ CustomError::InvalidOwner
+    )]
+    pub disputer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = arbitrator.key() == market.arbitrator @ CustomError::Unauthorized
+    )]
+    pub arbitrator: Signer<'info>, // Only the arbitrator can rule on disputes
+
+    pub token_program: Program<'info, Token>,
+}
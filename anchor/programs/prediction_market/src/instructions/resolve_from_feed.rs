@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::config::Config;
+use crate::state::market::{Market, MarketType, PriceComparison, ResolutionMode};
+use crate::price_feed::{parse_price_account, PYTH_STATUS_TRADING};
+use crate::constants::SCALAR_LONG_INDEX;
+use crate::error::CustomError;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<ResolveFromFeed>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+    let config = market.price_feed;
+
+    // Validations
+    require!(
+        market.resolution_mode == ResolutionMode::PriceFeed,
+        CustomError::InvalidResolutionMode
+    );
+    require!(!market.market_settled, CustomError::MarketAlreadySettled);
+    require!(
+        current_time >= market.end_timestamp,
+        CustomError::MarketNotExpired
+    );
+
+    let feed = parse_price_account(&ctx.accounts.price_feed.try_borrow_data()?)?;
+    require!(feed.status == PYTH_STATUS_TRADING, CustomError::PriceNotTrading);
+
+    // Only prices published within max_staleness of expiry count, so a caller can't
+    // wait for a favorable tick long after the market ended
+    require!(
+        feed.publish_time >= market.end_timestamp
+            && feed.publish_time <= market.end_timestamp.saturating_add(config.max_staleness),
+        CustomError::StalePrice
+    );
+    require!(
+        (feed.conf as u128) * 10000
+            <= (config.max_confidence_bps as u128) * (feed.price.unsigned_abs() as u128),
+        CustomError::PriceConfidenceTooWide
+    );
+
+    let payout_numerators = match market.market_type {
+        MarketType::Categorical => {
+            let condition_met = match config.comparison {
+                PriceComparison::Above => feed.price > config.threshold,
+                PriceComparison::Below => feed.price < config.threshold,
+            };
+            // The first outcome wins when the condition holds
+            let winning_outcome = u64::from(!condition_met);
+            market.winning_outcome = winning_outcome;
+            (0..market.outcomes.len() as u64)
+                .map(|i| u64::from(i == winning_outcome))
+                .collect()
+        }
+        MarketType::Scalar => {
+            let resolved_value = feed.price.clamp(market.lower_bound, market.upper_bound);
+            market.resolved_value = resolved_value;
+            let mut payout_numerators = vec![0; market.outcomes.len()];
+            payout_numerators[SCALAR_LONG_INDEX] = resolved_value.abs_diff(market.lower_bound);
+            payout_numerators[1 - SCALAR_LONG_INDEX] = market.upper_bound.abs_diff(resolved_value);
+            payout_numerators
+        }
+    };
+
+    market.market_closed = true;
+    market.settle(payout_numerators)?;
+    msg!(
+        "Market {} settled from feed price {} (expo {}, conf {}, published {})",
+        market.market_id,
+        feed.price,
+        feed.expo,
+        feed.conf,
+        feed.publish_time
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveFromFeed<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: must be the feed configured on the market, owned by the configured feed program
+    /// and parsed as a Pyth price account
+    #[account(
+        address = market.price_feed.feed @ CustomError::InvalidPriceFeed,
+        owner = config.price_feed_program @ CustomError::InvalidPriceFeed
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+}
@@ -1,20 +1,36 @@
 // src/instructions/sell_shares.rs
 
 use crate::error::CustomError;
+use crate::permit::check_access;
+use crate::state::access::{Allowance, PermitNonce};
+use crate::state::config::Config;
 use crate::state::market::Market;
-use crate::utils::{calculate_cost, calculate_fee};
+use crate::state::position::Position;
+use crate::state::referrer::ReferrerStats;
+use crate::utils::{assert_solvent, calculate_fee};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 
-pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) -> Result<()> {
+pub(crate) fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let outcome_mint = &ctx.accounts.outcome_mint;
     let buyer_share_account = &ctx.accounts.buyer_share_account;
 
     // Validation checks
-    require!(!market.market_closed, CustomError::MarketClosed);
+    require!(!ctx.accounts.config.paused, CustomError::ProtocolPaused);
+    require!(!market.paused, CustomError::MarketPaused);
+    let current_time = Clock::get()?.unix_timestamp;
+    check_access(
+        market,
+        &ctx.accounts.seller.key(),
+        ctx.accounts.allowance.as_ref(),
+        ctx.accounts.instructions_sysvar.as_ref().map(|ix| ix.as_ref()),
+        ctx.accounts.permit_nonce.as_mut(),
+        current_time,
+    )?;
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
     require!(
         outcome_index < market.outcomes.len() as u64,
         CustomError::InvalidOutcome
@@ -38,9 +54,11 @@ pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) ->
         CustomError::InsufficientShares
     );
 
-    // Calculate LMSR refund
-    let q_before: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
-    let cost_before = calculate_cost(&q_before, market.b)?;
+    // Calculate the refund on the market's curve
+    let q_before: Vec<u64> = market.outcome_shares();
+    let refund_amount = market
+        .pricing_curve()
+        .sell_refund(&q_before, outcome_index as usize, num_shares)?;
 
     let outcome = &mut market.outcomes[outcome_index as usize];
     outcome.total_shares = outcome
@@ -48,19 +66,24 @@ pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) ->
         .checked_sub(num_shares)
         .ok_or(CustomError::MathError)?;
 
-    let q_after: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
-    let cost_after = calculate_cost(&q_after, market.b)?;
-
-    let refund_amount = cost_before
-        .checked_sub(cost_after)
-        .ok_or(CustomError::MathError)? as u64;
+    // Selling frees room under the position limit; shares received from others
+    // were never counted, so the position bottoms out at zero
+    let position = &mut ctx.accounts.position;
+    position.num_shares = position.num_shares.saturating_sub(num_shares);
+    position.market = market.key();
+    position.user = ctx.accounts.seller.key();
+    position.outcome_index = outcome_index;
+    position.bump = ctx.bumps.position;
 
     // Calculate fee
     let fee_amount: u64 = calculate_fee(refund_amount, market.fee_percent)?;
-    let reinvest_amount: u64 = fee_amount.checked_div(2).ok_or(CustomError::Overflow)?;
-    let fee_recipient_amount: u64 = fee_amount
-        .checked_sub(reinvest_amount)
-        .ok_or(CustomError::Overflow)?;
+    let referral_amount: u64 = if ctx.accounts.referrer_stats.is_some() {
+        market.referral_fee(fee_amount)?
+    } else {
+        0
+    };
+    let (reinvest_amount, fee_recipient_amount, protocol_fee_amount) =
+        market.split_fee(fee_amount - referral_amount)?;
     let net_refund: u64 = refund_amount
         .checked_sub(fee_amount)
         .ok_or(CustomError::Overflow)?;
@@ -68,6 +91,8 @@ pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) ->
     msg!("Fee Amount: {}", fee_amount);
     msg!("Reinvest Amount: {}", reinvest_amount);
     msg!("Fee Recipient Amount: {}", fee_recipient_amount);
+    msg!("Protocol Fee Amount: {}", protocol_fee_amount);
+    msg!("Referral Fee Amount: {}", referral_amount);
     msg!("Total Refund Amount: {}", refund_amount);
     msg!("Net Refund (Cost - Fee): {}", net_refund);
 
@@ -109,11 +134,40 @@ pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) ->
         net_refund
     );
 
-    // Update market funds
+    // Update market funds; the fee stays in the vault and is ledgered like on buys
     market.market_maker_funds = market
         .market_maker_funds
         .checked_sub(refund_amount)
+        .and_then(|funds| funds.checked_add(reinvest_amount))
+        .ok_or(CustomError::Underflow)?;
+    market.collected_fees = market
+        .collected_fees
+        .checked_add(fee_recipient_amount)
+        .ok_or(CustomError::Overflow)?;
+    market.protocol_fees = market
+        .protocol_fees
+        .checked_add(protocol_fee_amount)
         .ok_or(CustomError::Overflow)?;
+    if let Some(referrer_stats) = ctx.accounts.referrer_stats.as_mut() {
+        market.referral_fees = market
+            .referral_fees
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+        referrer_stats.accrued_fees = referrer_stats
+            .accrued_fees
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+        referrer_stats.total_earned = referrer_stats
+            .total_earned
+            .checked_add(referral_amount)
+            .ok_or(CustomError::Overflow)?;
+    }
+
+    market.adapt_liquidity(reinvest_amount)?;
+
+    market
+        .pricing_curve()
+        .check_invariant(&market.outcome_shares(), market.market_maker_funds)?;
 
     // Emit event
     msg!(
@@ -123,6 +177,9 @@ pub fn handler(ctx: Context<SellShares>, outcome_index: u64, num_shares: u64) ->
         refund_amount
     );
 
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
     Ok(())
 }
 
@@ -132,6 +189,35 @@ pub struct SellShares<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Approval on gated markets, not needed with a permit
+    #[account(seeds = [b"allowance", market.key().as_ref(), seller.key().as_ref()], bump = allowance.bump)]
+    pub allowance: Option<Account<'info, Allowance>>,
+
+    /// CHECK: instructions sysvar, read for an ed25519 permit on gated markets
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Nonce consumed by a permit, needed only when trading on one
+    #[account(
+        init_if_needed,
+        seeds = [b"permit", market.key().as_ref(), seller.key().as_ref()],
+        bump,
+        payer = seller,
+        space = 8 + PermitNonce::INIT_SPACE,
+    )]
+    pub permit_nonce: Option<Account<'info, PermitNonce>>,
+
+    /// Referrer credited with part of the fee
+    #[account(
+        mut,
+        constraint = referrer_stats.market == market.key() @ CustomError::InvalidAccounts,
+        constraint = referrer_stats.referrer != seller.key() @ CustomError::SelfReferral
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
     #[account(
         mut,
         associated_token::mint = outcome_mint,
@@ -141,7 +227,7 @@ pub struct SellShares<'info> {
 
     #[account(
         mut,
-        constraint = outcome_mint.key() == market.outcomes[outcome_index as usize].mint
+        constraint = market.outcomes.get(outcome_index as usize).map(|o| o.mint) == Some(outcome_mint.key()) @ CustomError::InvalidOutcome
     )]
     pub outcome_mint: Account<'info, Mint>,
 
@@ -159,6 +245,15 @@ pub struct SellShares<'info> {
     )]
     pub market_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"position", market.key().as_ref(), seller.key().as_ref(), &outcome_index.to_le_bytes()],
+        bump,
+        payer = seller,
+        space = 8 + Position::INIT_SPACE,
+    )]
+    pub position: Account<'info, Position>,
+
     #[account(mut)]
     pub seller: Signer<'info>,
 
@@ -1,43 +0,0 @@
-use anchor_lang::prelude::*;
-use crate::state::market::Market;
-use crate::error::CustomError;
-
-pub fn handler(ctx: Context<SetOutcome>, winning_outcome: u64) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-
-    // Ensure the market is closed before setting the outcome
-    require!(market.market_closed, CustomError::MarketNotClosed);
-
-    // Ensure the market is not already settled
-    require!(!market.market_settled, CustomError::MarketAlreadySettled);
-
-    // Validate the winning outcome index
-    require!(
-        (winning_outcome as usize) < market.outcomes.len(),
-        CustomError::InvalidOutcome
-    );
-
-    // Set the winning outcome
-    market.winning_outcome = winning_outcome;
-    market.market_settled = true;
-    msg!(
-        "Market {} settled with winning outcome {}",
-        market.market_id,
-        winning_outcome
-    );
-
-    Ok(())
-}
-
-#[derive(Accounts)]
-pub struct SetOutcome<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    #[account(
-        signer, // Ensure this account is signed
-        //address = market.oracle // Ensure this is the oracle account defined in the market
-    )] // Ensure the oracle is correct
-    pub oracle: Signer<'info>,          // Only the oracle can set the outcome
-}
-
-
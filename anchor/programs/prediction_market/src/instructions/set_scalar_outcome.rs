@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::market::{Market, MarketType};
+use crate::error::CustomError;
+use crate::constants::SCALAR_LONG_INDEX;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<SetScalarOutcome>, value: i64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Ensure the market is closed before setting the outcome
+    require!(market.market_closed, CustomError::MarketNotClosed);
+
+    require!(
+        market.market_type == MarketType::Scalar,
+        CustomError::InvalidMarketType
+    );
+
+    // Values outside the range pay everything to one side
+    let resolved_value = value.clamp(market.lower_bound, market.upper_bound);
+
+    // LONG receives the distance from the lower bound, SHORT the rest of the range
+    let long_numerator = resolved_value.abs_diff(market.lower_bound);
+    let short_numerator = market.upper_bound.abs_diff(resolved_value);
+    let mut payout_numerators = vec![0; market.outcomes.len()];
+    payout_numerators[SCALAR_LONG_INDEX] = long_numerator;
+    payout_numerators[1 - SCALAR_LONG_INDEX] = short_numerator;
+
+    market.resolved_value = resolved_value;
+    market.propose_payouts(payout_numerators, current_time)?;
+    msg!(
+        "Scalar market {} proposed value {} (reported {})",
+        market.market_id,
+        resolved_value,
+        value
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetScalarOutcome<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = oracle.key() == market.oracle @ CustomError::Unauthorized
+    )]
+    pub oracle: Signer<'info>, // Only the oracle can report the value
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::market::Market;
+use crate::error::CustomError;
+use crate::events::EndTimeUpdated;
+
+pub(crate) fn handler(ctx: Context<UpdateEndTime>, new_end_timestamp: i64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // An expired or closed market cannot be reopened
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
+    require!(
+        new_end_timestamp > current_time && new_end_timestamp > market.start_timestamp,
+        CustomError::InvalidDuration
+    );
+
+    let old_end_timestamp = market.end_timestamp;
+    market.end_timestamp = new_end_timestamp;
+
+    emit!(EndTimeUpdated {
+        market: market.key(),
+        market_id: market.market_id,
+        old_end_timestamp,
+        new_end_timestamp,
+        updated_by: ctx.accounts.signer.key(),
+    });
+    msg!(
+        "Market {} end time moved from {} to {}",
+        market.market_id,
+        old_end_timestamp,
+        new_end_timestamp
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateEndTime<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        constraint = signer.key() == market.authority || signer.key() == market.oracle @ CustomError::Unauthorized
+    )]
+    pub signer: Signer<'info>, // The market authority or its oracle
+}
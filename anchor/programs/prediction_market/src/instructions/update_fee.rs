@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::market::Market;
+use crate::error::CustomError;
+use crate::events::FeeUpdated;
+use crate::utils::assert_solvent;
+
+pub(crate) fn handler(ctx: Context<UpdateFee>, new_fee_percent: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Traders priced their positions against the current fee, so it can only go down
+    require!(market.trading_open(current_time), CustomError::MarketClosed);
+    require!(
+        new_fee_percent < market.fee_percent,
+        CustomError::FeeIncreaseNotAllowed
+    );
+
+    let old_fee_percent = market.fee_percent;
+    market.fee_percent = new_fee_percent;
+
+    emit!(FeeUpdated {
+        market: market.key(),
+        market_id: market.market_id,
+        old_fee_percent,
+        new_fee_percent,
+    });
+    msg!(
+        "Market {} fee lowered from {} to {} bps",
+        market.market_id,
+        old_fee_percent,
+        new_fee_percent
+    );
+
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
+    pub market_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = authority.key() == market.authority @ CustomError::Unauthorized
+    )]
+    pub authority: Signer<'info>, // Only the market authority can change the fee
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::market::Market;
+use crate::utils::assert_solvent;
 use crate::error::CustomError;
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<WithdrawFees>,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
@@ -30,6 +31,7 @@ pub fn handler(
         signer_seeds,
     );
     token::transfer(refund_transfer_ctx, fees)?;
+    market.collected_fees = 0;
     msg!(
         "Transferred {} tokens from market to fee recipients's token  account",
         fees
@@ -38,6 +40,9 @@ pub fn handler(
     // Emit event
     msg!("Fees Withdrawn: {} tokens to fee recipient", fees);
 
+    ctx.accounts.market_token_account.reload()?;
+    assert_solvent(&ctx.accounts.market, ctx.accounts.market_token_account.amount)?;
+
     Ok(())
 }
 
@@ -46,7 +51,11 @@ pub struct WithdrawFees<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = market.base_token_mint,
+        associated_token::authority = market
+    )]
     pub market_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
@@ -1,14 +1,20 @@
 #![allow(clippy::result_large_err)]
+#![allow(clippy::too_many_arguments)]
 
 use anchor_lang::prelude::*;
 
 pub mod constants;
+pub mod curves;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod permit;
+pub mod price_feed;
 pub mod state;
 pub mod utils;
 
 pub use crate::instructions::*;
+pub use crate::state::market::{CurveType, MarketType, PriceComparison, PriceFeedConfig, ResolutionMode};
 
 declare_id!("AeHTqTh19seFqLVKvbrkEhgCJEzUWPJu2vxYppdoj6BL");
 
@@ -16,6 +22,52 @@ declare_id!("AeHTqTh19seFqLVKvbrkEhgCJEzUWPJu2vxYppdoj6BL");
 pub mod prediction_market {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        protocol_fee_bps: u64,
+        treasury: Pubkey,
+        max_fee_bps: u64,
+        allowed_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::config::initialize::handler(ctx, protocol_fee_bps, treasury, max_fee_bps, allowed_mints)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        new_admin: Option<Pubkey>,
+        protocol_fee_bps: Option<u64>,
+        treasury: Option<Pubkey>,
+        max_fee_bps: Option<u64>,
+        paused: Option<bool>,
+        price_feed_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::config::update::handler(
+            ctx,
+            new_admin,
+            protocol_fee_bps,
+            treasury,
+            max_fee_bps,
+            paused,
+            price_feed_program,
+        )
+    }
+
+    pub fn add_allowed_mint(ctx: Context<AddAllowedMint>, mint: Pubkey) -> Result<()> {
+        instructions::config::add_allowed_mint::handler(ctx, mint)
+    }
+
+    pub fn remove_allowed_mint(ctx: Context<RemoveAllowedMint>, mint: Pubkey) -> Result<()> {
+        instructions::config::remove_allowed_mint::handler(ctx, mint)
+    }
+
+    pub fn grant_allowance(ctx: Context<GrantAllowance>, user: Pubkey) -> Result<()> {
+        instructions::access::grant::handler(ctx, user)
+    }
+
+    pub fn revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+        instructions::access::revoke::handler(ctx)
+    }
+
     pub fn create_market<'info>(
         ctx: Context<'_, '_, '_, 'info, CreateMarket<'info>>,
         market_id: u64,
@@ -23,10 +75,29 @@ pub mod prediction_market {
         outcomes: Vec<String>,
         oracle: Pubkey,
         b: u64,
-        duration: i64,
+        start_timestamp: i64,
+        end_timestamp: i64,
         fee_percent: u64,
         fee_recipient: Pubkey,
         initial_funds: u64,
+        curve: CurveType,
+        alpha: u64,
+        market_type: MarketType,
+        lower_bound: i64,
+        upper_bound: i64,
+        challenge_period: i64,
+        arbitrator: Pubkey,
+        dispute_bond: u64,
+        resolution_mode: ResolutionMode,
+        price_feed: PriceFeedConfig,
+        lp_fee_bps: u64,
+        creator_fee_bps: u64,
+        liquidity_growth_bps: u64,
+        max_b: u64,
+        max_shares_per_user: u64,
+        max_total_shares_per_outcome: u64,
+        access_authority: Pubkey,
+        referral_fee_bps: u64,
     ) -> Result<()> {
         instructions::create_market::handler(
             ctx,
@@ -35,10 +106,29 @@ pub mod prediction_market {
             outcomes,
             oracle,
             b,
-            duration,
+            start_timestamp,
+            end_timestamp,
             fee_percent,
             fee_recipient,
             initial_funds,
+            curve,
+            alpha,
+            market_type,
+            lower_bound,
+            upper_bound,
+            challenge_period,
+            arbitrator,
+            dispute_bond,
+            resolution_mode,
+            price_feed,
+            lp_fee_bps,
+            creator_fee_bps,
+            liquidity_growth_bps,
+            max_b,
+            max_shares_per_user,
+            max_total_shares_per_outcome,
+            access_authority,
+            referral_fee_bps,
         )
     }
 
@@ -58,14 +148,105 @@ pub mod prediction_market {
         instructions::close_market::handler(ctx)
     }
 
-    pub fn set_outcome(ctx: Context<SetOutcome>, winning_outcome: u64) -> Result<()> {
-        instructions::set_outcome::handler(ctx, winning_outcome)
+    pub fn update_end_time(ctx: Context<UpdateEndTime>, new_end_timestamp: i64) -> Result<()> {
+        instructions::update_end_time::handler(ctx, new_end_timestamp)
+    }
+
+    pub fn update_fee(ctx: Context<UpdateFee>, new_fee_percent: u64) -> Result<()> {
+        instructions::update_fee::handler(ctx, new_fee_percent)
+    }
+
+    pub fn pause_market(ctx: Context<PauseMarket>) -> Result<()> {
+        instructions::pause_market::handler(ctx)
+    }
+
+    pub fn unpause_market(ctx: Context<UnpauseMarket>) -> Result<()> {
+        instructions::unpause_market::handler(ctx)
+    }
+
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, winning_outcome: u64) -> Result<()> {
+        instructions::propose_outcome::handler(ctx, winning_outcome)
+    }
+    /// Kept for existing clients, proposes the outcome like `propose_outcome`
+    pub fn set_outcome(ctx: Context<ProposeOutcome>, winning_outcome: u64) -> Result<()> {
+        instructions::propose_outcome::handler(ctx, winning_outcome)
+    }
+    pub fn set_scalar_outcome(ctx: Context<SetScalarOutcome>, value: i64) -> Result<()> {
+        instructions::set_scalar_outcome::handler(ctx, value)
+    }
+    pub fn report_payouts(ctx: Context<ReportPayouts>, payout_numerators: Vec<u64>) -> Result<()> {
+        instructions::report_payouts::handler(ctx, payout_numerators)
+    }
+    pub fn invalidate_market(ctx: Context<InvalidateMarket>) -> Result<()> {
+        instructions::invalidate_market::handler(ctx)
+    }
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+        instructions::dispute_outcome::handler(ctx)
+    }
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, payout_numerators: Vec<u64>) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, payout_numerators)
+    }
+    pub fn propose_optimistic_outcome(
+        ctx: Context<ProposeOptimisticOutcome>,
+        payout_numerators: Vec<u64>,
+    ) -> Result<()> {
+        instructions::optimistic_oracle::propose::handler(ctx, payout_numerators)
+    }
+    pub fn dispute_optimistic_outcome(ctx: Context<DisputeOptimisticOutcome>) -> Result<()> {
+        instructions::optimistic_oracle::dispute::handler(ctx)
+    }
+    pub fn settle_optimistic_outcome(ctx: Context<SettleOptimisticOutcome>) -> Result<()> {
+        instructions::optimistic_oracle::settle::handler(ctx)
+    }
+    pub fn resolve_optimistic_dispute(
+        ctx: Context<ResolveOptimisticDispute>,
+        payout_numerators: Vec<u64>,
+    ) -> Result<()> {
+        instructions::optimistic_oracle::resolve::handler(ctx, payout_numerators)
+    }
+    pub fn create_committee(
+        ctx: Context<CreateCommittee>,
+        committee_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::committee::create::handler(ctx, committee_id, members, threshold)
+    }
+    pub fn submit_vote(ctx: Context<SubmitVote>, outcome_index: u64) -> Result<()> {
+        instructions::committee::vote::handler(ctx, outcome_index)
+    }
+    pub fn resolve_from_feed(ctx: Context<ResolveFromFeed>) -> Result<()> {
+        instructions::resolve_from_feed::handler(ctx)
     }
     pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
         instructions::claim_payout::handler(ctx)
     }
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+        instructions::liquidity::add::handler(ctx, amount)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, lp_amount: u64) -> Result<()> {
+        instructions::liquidity::remove::handler(ctx, lp_amount)
+    }
+
+    pub fn increase_liquidity(ctx: Context<IncreaseLiquidity>, new_b: u64) -> Result<()> {
+        instructions::liquidity::increase::handler(ctx, new_b)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::referral::register::handler(ctx)
+    }
+
+    pub fn withdraw_referral_fees(ctx: Context<WithdrawReferralFees>) -> Result<()> {
+        instructions::referral::withdraw::handler(ctx)
+    }
+
     pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
         instructions::withdraw_fees::handler(ctx)
     }
+
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+        instructions::withdraw_protocol_fees::handler(ctx)
+    }
 }
 
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::error::CustomError;
+use crate::state::access::{Allowance, PermitNonce};
+use crate::state::market::Market;
+
+/// Permit message signed by a market's access authority: `market || user || expires_at || nonce`
+pub const PERMIT_MESSAGE_LEN: usize = 80;
+
+// Layout of the ed25519 program's instruction data with a single signature
+const SIGNATURE_COUNT_OFFSET: usize = 0;
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+
+/// Offsets in an ed25519 instruction that point into its own data
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(error!(CustomError::InvalidPermit))
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    data.get(offset as usize..offset as usize + len)
+        .ok_or(error!(CustomError::InvalidPermit))
+}
+
+/// Checks that the instruction before the current one verifies a permit for
+/// `user` on `market`, signed by `authority` and not yet expired, and returns its nonce.
+/// The ed25519 program has already checked the signature when this runs.
+pub fn verify_permit(
+    instructions_sysvar: &AccountInfo,
+    market: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+    now: i64,
+) -> Result<u64> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, CustomError::InvalidPermit);
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require!(ix.program_id == ed25519_program::ID, CustomError::InvalidPermit);
+
+    let data = &ix.data;
+    require!(
+        data.get(SIGNATURE_COUNT_OFFSET) == Some(&1) && data.len() >= OFFSETS_START + OFFSETS_LEN,
+        CustomError::InvalidPermit
+    );
+    let signature_ix = read_u16(data, OFFSETS_START + 2)?;
+    let pubkey_offset = read_u16(data, OFFSETS_START + 4)?;
+    let pubkey_ix = read_u16(data, OFFSETS_START + 6)?;
+    let message_offset = read_u16(data, OFFSETS_START + 8)?;
+    let message_len = read_u16(data, OFFSETS_START + 10)?;
+    let message_ix = read_u16(data, OFFSETS_START + 12)?;
+
+    // The signature, key and message must all live in the ed25519 instruction itself
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION
+            && message_len as usize == PERMIT_MESSAGE_LEN,
+        CustomError::InvalidPermit
+    );
+
+    let signer = read_slice(data, pubkey_offset, PUBKEY_LEN)?;
+    require!(signer == authority.as_ref(), CustomError::InvalidPermit);
+
+    let message = read_slice(data, message_offset, PERMIT_MESSAGE_LEN)?;
+    let expires_at = i64::from_le_bytes(
+        message[64..72].try_into().map_err(|_| error!(CustomError::InvalidPermit))?,
+    );
+    let nonce = u64::from_le_bytes(
+        message[72..80].try_into().map_err(|_| error!(CustomError::InvalidPermit))?,
+    );
+    require!(
+        &message[0..32] == market.as_ref() && &message[32..64] == user.as_ref() && now <= expires_at,
+        CustomError::InvalidPermit
+    );
+    Ok(nonce)
+}
+
+/// Lets `user` trade on `market` when it is open to everyone, holds an
+/// allowance, or presents a permit from the access authority carrying the
+/// user's current nonce, which the permit then consumes
+pub fn check_access(
+    market: &Account<Market>,
+    user: &Pubkey,
+    allowance: Option<&Account<Allowance>>,
+    instructions_sysvar: Option<&AccountInfo>,
+    permit_nonce: Option<&mut Account<PermitNonce>>,
+    now: i64,
+) -> Result<()> {
+    if market.access_authority == Pubkey::default() || allowance.is_some() {
+        return Ok(());
+    }
+
+    let instructions_sysvar = instructions_sysvar.ok_or(CustomError::AccessDenied)?;
+    let nonce = verify_permit(
+        instructions_sysvar,
+        &market.key(),
+        &market.access_authority,
+        user,
+        now,
+    )?;
+
+    let permit_nonce = permit_nonce.ok_or(CustomError::InvalidPermit)?;
+    require!(nonce == permit_nonce.nonce, CustomError::PermitAlreadyUsed);
+    permit_nonce.market = market.key();
+    permit_nonce.user = *user;
+    permit_nonce.nonce = nonce.checked_add(1).ok_or(CustomError::Overflow)?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::error::CustomError;
+
+/// Pyth oracle program, the default owner of accepted price accounts
+pub const PYTH_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Magic number at the start of every Pyth v2 account
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+
+/// Pyth account type of a price account
+pub const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+
+/// Pyth aggregate status while publishers are trading
+pub const PYTH_STATUS_TRADING: u32 = 1;
+
+// Byte offsets in the Pyth v2 price account layout
+const MAGIC_OFFSET: usize = 0;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_CONF_OFFSET: usize = 216;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth-style price account
+pub struct PriceFeedData {
+    /// Price in units of `10^expo`
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: u32,
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(error!(CustomError::InvalidPriceFeed))
+}
+
+/// Parses the aggregate price of a Pyth v2 price account
+pub fn parse_price_account(data: &[u8]) -> Result<PriceFeedData> {
+    require!(data.len() >= PRICE_ACCOUNT_MIN_LEN, CustomError::InvalidPriceFeed);
+    require!(
+        u32::from_le_bytes(read_bytes(data, MAGIC_OFFSET)?) == PYTH_MAGIC,
+        CustomError::InvalidPriceFeed
+    );
+    require!(
+        u32::from_le_bytes(read_bytes(data, ACCOUNT_TYPE_OFFSET)?) == PYTH_PRICE_ACCOUNT_TYPE,
+        CustomError::InvalidPriceFeed
+    );
+
+    Ok(PriceFeedData {
+        price: i64::from_le_bytes(read_bytes(data, AGGREGATE_PRICE_OFFSET)?),
+        conf: u64::from_le_bytes(read_bytes(data, AGGREGATE_CONF_OFFSET)?),
+        expo: i32::from_le_bytes(read_bytes(data, EXPONENT_OFFSET)?),
+        publish_time: i64::from_le_bytes(read_bytes(data, TIMESTAMP_OFFSET)?),
+        status: u32::from_le_bytes(read_bytes(data, AGGREGATE_STATUS_OFFSET)?),
+    })
+}
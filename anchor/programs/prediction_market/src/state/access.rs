@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Approval for `user` to trade on a gated market, granted by its access authority
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+}
+
+/// Nonce the next permit for `user` on a gated market must carry. Each trade on a
+/// permit consumes it, and revoking the user's allowance skips it, so a permit works
+/// at most once and none issued before a revoke can be used after it.
+#[account]
+#[derive(InitSpace)]
+pub struct PermitNonce {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub nonce: u64,
+}
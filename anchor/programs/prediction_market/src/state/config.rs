@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-wide settings, a singleton PDA consulted by every market
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    /// Protocol's share of every trading fee, in basis points of the fee
+    pub protocol_fee_bps: u64,
+    /// Receives protocol fees
+    pub treasury: Pubkey,
+    /// Base mints markets can be created with
+    #[max_len(10)]
+    pub allowed_mints: Vec<Pubkey>,
+    /// Highest fee a market can charge, in basis points
+    pub max_fee_bps: u64,
+    /// Blocks market creation and trading on every market while set
+    pub paused: bool,
+    /// Program that must own the price account of a price-feed market
+    pub price_feed_program: Pubkey,
+    pub bump: u8,
+}
+
+impl Config {
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints.contains(mint)
+    }
+}
@@ -1,6 +1,66 @@
 use anchor_lang::prelude::*;
+use crate::constants::{MAX_FEE_BPS, PAYOUT_PER_SHARE, SCALE, SHARES_DECIMALS};
+use crate::error::CustomError;
 use crate::state::outcome::Outcome;
+use crate::curves::{pricing_curve, PricingCurve};
 
+/// Cost function used to price trades in a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(InitSpace)]
+pub enum CurveType {
+    /// Classic LMSR with a fixed liquidity parameter `b`
+    Lmsr,
+    /// Liquidity-sensitive LMSR where `b` grows with `alpha * Σ q_i`
+    LsLmsr,
+    /// Constant-product pricing for binary markets as a cost function, `b` is the pool depth
+    Cpmm,
+}
+
+/// How a market's outcomes pay out at settlement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(InitSpace)]
+pub enum MarketType {
+    /// One winning outcome pays `PAYOUT_PER_SHARE`
+    Categorical,
+    /// LONG/SHORT outcomes split `PAYOUT_PER_SHARE` linearly between the bounds
+    Scalar,
+}
+
+/// Who is allowed to resolve a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(InitSpace)]
+pub enum ResolutionMode {
+    /// The market's `oracle` proposes the result
+    Oracle,
+    /// Anyone proposes with a bond, see `OptimisticResolution`
+    Optimistic,
+    /// `oracle` is a `ResolverCommittee` whose members vote on the outcome
+    Committee,
+    /// Settled permissionlessly from the price account in `price_feed`
+    PriceFeed,
+}
+
+/// Condition under which the first outcome of a price-feed market wins
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(InitSpace)]
+pub enum PriceComparison {
+    Above,
+    Below,
+}
+
+/// Price account and checks used by `ResolutionMode::PriceFeed`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(InitSpace)]
+pub struct PriceFeedConfig {
+    pub feed: Pubkey,
+    /// Compared against the feed price, in the feed's exponent
+    pub threshold: i64,
+    pub comparison: PriceComparison,
+    /// Latest accepted publish time, in seconds after `end_timestamp`
+    pub max_staleness: i64,
+    /// Widest accepted confidence interval relative to the price
+    pub max_confidence_bps: u64,
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -23,5 +83,298 @@ pub struct Market {
     pub collected_fees: u64,
     pub base_token_mint: Pubkey,
     pub bump: u8,
+    pub curve_type: CurveType,
+    /// LS-LMSR liquidity sensitivity, scaled by `SCALE`
+    pub alpha: u64,
+    pub market_type: MarketType,
+    /// Scalar range, only used by scalar markets
+    pub lower_bound: i64,
+    pub upper_bound: i64,
+    /// Reported value of a settled scalar market
+    pub resolved_value: i64,
+    /// Share of `PAYOUT_PER_SHARE` paid per outcome, `numerator_i / payout_denominator`
+    #[max_len(10)]
+    pub payout_numerators: Vec<u64>,
+    pub payout_denominator: u64,
+    /// Set when the event was cancelled and holders are refunded at last prices
+    pub market_invalid: bool,
+    /// Seconds a proposed result can be disputed before it becomes final
+    pub challenge_period: i64,
+    /// Rules on disputed results
+    pub arbitrator: Pubkey,
+    /// Base tokens a disputer must post
+    pub dispute_bond: u64,
+    pub outcome_proposed: bool,
+    pub outcome_proposed_at: i64,
+    pub outcome_disputed: bool,
+    pub disputer: Pubkey,
+    pub resolution_mode: ResolutionMode,
+    /// Committee votes per outcome, followed by the votes to invalidate the market
+    #[max_len(11)]
+    pub committee_votes: Vec<u8>,
+    pub price_feed: PriceFeedConfig,
+    /// Trading opens at this time, markets can be created and funded ahead of it
+    pub start_timestamp: i64,
+    /// Creator of the market, allowed to manage it alongside the oracle
+    pub authority: Pubkey,
+    /// Blocks trading and claims while set
+    pub paused: bool,
+    /// Split of every trading fee in basis points of the fee, summing to `MAX_FEE_BPS`.
+    /// The LP share is reinvested into `market_maker_funds`, the creator share goes to
+    /// `collected_fees` and the protocol share to `protocol_fees`.
+    pub lp_fee_bps: u64,
+    pub creator_fee_bps: u64,
+    pub protocol_fee_bps: u64,
+    /// Protocol fees and forfeited dispute bonds owed to the config treasury
+    pub protocol_fees: u64,
+    /// Bump of the LP mint PDA, `[b"lp_mint", market]`
+    pub lp_mint_bump: u8,
+    /// Largest growth of `b` in one trade, in basis points; zero keeps `b` fixed
+    pub liquidity_growth_bps: u64,
+    /// Adaptive growth never takes `b` above this cap
+    pub max_b: u64,
+    /// Most shares of one outcome a single user can hold, counted as bought net of sold
+    /// in their `Position`, zero for no limit
+    pub max_shares_per_user: u64,
+    /// Most outstanding shares of any outcome, zero for no limit
+    pub max_total_shares_per_outcome: u64,
+    /// Approves traders on gated markets, the default key leaves the market open to everyone
+    pub access_authority: Pubkey,
+    /// Share of the fee paid to a trade's referrer, in basis points of the fee
+    pub referral_fee_bps: u64,
+    /// Referral fees owed across all `ReferrerStats` of this market
+    pub referral_fees: u64,
     //pub outcome_mint: Pubkey, 
 }
+
+impl Market {
+    /// Pricing curve selected by `curve_type`
+    pub fn pricing_curve(&self) -> Box<dyn PricingCurve> {
+        pricing_curve(self.curve_type, self.b, self.alpha)
+    }
+
+    /// Whether shares can be traded at `now`; trading ends at `end_timestamp` even before `close_market`
+    pub fn trading_open(&self, now: i64) -> bool {
+        !self.market_closed && now < self.end_timestamp
+    }
+
+    /// Referrer's portion of `fee_amount`, taken before the fee is split
+    pub fn referral_fee(&self, fee_amount: u64) -> Result<u64> {
+        let amount = (fee_amount as u128)
+            .checked_mul(self.referral_fee_bps as u128)
+            .and_then(|a| a.checked_div(MAX_FEE_BPS as u128))
+            .ok_or(CustomError::Overflow)?;
+        Ok(amount as u64)
+    }
+
+    /// Splits `fee_amount` into its (LP, creator, protocol) portions; rounding dust goes to the LP
+    pub fn split_fee(&self, fee_amount: u64) -> Result<(u64, u64, u64)> {
+        let portion = |bps: u64| -> Result<u64> {
+            let amount = (fee_amount as u128)
+                .checked_mul(bps as u128)
+                .and_then(|a| a.checked_div(MAX_FEE_BPS as u128))
+                .ok_or(CustomError::Overflow)?;
+            Ok(amount as u64)
+        };
+        let creator_amount = portion(self.creator_fee_bps)?;
+        let protocol_amount = portion(self.protocol_fee_bps)?;
+        let lp_amount = fee_amount
+            .checked_sub(creator_amount)
+            .and_then(|a| a.checked_sub(protocol_amount))
+            .ok_or(CustomError::Underflow)?;
+        Ok((lp_amount, creator_amount, protocol_amount))
+    }
+
+    /// Pricing shares of each outcome, the `q` vector of the cost function.
+    /// Outstanding shares plus the virtual shares added by liquidity changes.
+    pub fn outcome_shares(&self) -> Vec<u64> {
+        self.outcomes
+            .iter()
+            .map(|o| o.total_shares.saturating_add(o.virtual_shares))
+            .collect()
+    }
+
+    /// Scales `b` and the pricing shares by `numerator / denominator`.
+    /// Every curve is homogeneous in `(q, b)`, so prices stay the same and the
+    /// cost function scales by the same factor.
+    pub fn rescale_liquidity(&mut self, numerator: u64, denominator: u64) -> Result<()> {
+        let scale = |value: u64| -> Result<u64> {
+            let scaled = (value as u128)
+                .checked_mul(numerator as u128)
+                .and_then(|v| v.checked_div(denominator as u128))
+                .ok_or(CustomError::MathError)?;
+            u64::try_from(scaled).map_err(|_| error!(CustomError::Overflow))
+        };
+
+        let b = scale(self.b)?;
+        require!(b > 0, CustomError::InvalidB);
+        self.b = b;
+
+        for outcome in self.outcomes.iter_mut() {
+            // Pricing shares can never drop below the shares that must be paid out
+            let pricing_shares = scale(
+                outcome
+                    .total_shares
+                    .checked_add(outcome.virtual_shares)
+                    .ok_or(CustomError::Overflow)?,
+            )?;
+            outcome.virtual_shares = pricing_shares
+                .checked_sub(outcome.total_shares)
+                .ok_or(CustomError::InvalidLiquidityAmount)?;
+        }
+        Ok(())
+    }
+
+    /// Grows `b` with the fees reinvested by a trade, keeping prices unchanged.
+    /// The growth costs at most `reinvest_amount`, so the market maker's funds keep
+    /// covering the cost function, and is bounded by `liquidity_growth_bps` and `max_b`.
+    pub fn adapt_liquidity(&mut self, reinvest_amount: u64) -> Result<()> {
+        if self.liquidity_growth_bps == 0 || reinvest_amount == 0 || self.b >= self.max_b {
+            return Ok(());
+        }
+
+        // Rescaling by b'/b scales the cost by the same factor, so the growth
+        // the reinvested fees pay for is b * reinvest / C(q)
+        let cost = self.pricing_curve().cost(&self.outcome_shares())?;
+        require!(cost > 0, CustomError::MathError);
+        let funded_growth = u64::try_from(self.b as u128 * reinvest_amount as u128 / cost as u128)
+            .map_err(|_| error!(CustomError::Overflow))?;
+        let max_growth = u64::try_from(self.b as u128 * self.liquidity_growth_bps as u128 / MAX_FEE_BPS as u128)
+            .map_err(|_| error!(CustomError::Overflow))?;
+        let new_b = self
+            .b
+            .saturating_add(funded_growth.min(max_growth))
+            .min(self.max_b);
+
+        if new_b > self.b {
+            let old_b = self.b;
+            self.rescale_liquidity(new_b, old_b)?;
+            msg!("Market {} b adapted from {} to {}", self.market_id, old_b, new_b);
+        }
+        Ok(())
+    }
+
+    /// Funds left to the LPs if every outstanding share were valued at its current price
+    pub fn lp_equity(&self) -> Result<u64> {
+        let curve = self.pricing_curve();
+        let q = self.outcome_shares();
+        let mut liability: u128 = 0;
+        for (i, outcome) in self.outcomes.iter().enumerate() {
+            liability += outcome.total_shares as u128 * curve.price(&q, i)? as u128;
+        }
+        let liability = liability * PAYOUT_PER_SHARE as u128
+            / SCALE as u128
+            / 10u128.pow(SHARES_DECIMALS);
+        Ok((self.market_maker_funds as u128).saturating_sub(liability) as u64)
+    }
+
+    /// Base tokens still owed to holders of outstanding shares once the market is settled
+    pub fn outstanding_payouts(&self) -> Result<u64> {
+        let mut payouts: u128 = 0;
+        for (i, outcome) in self.outcomes.iter().enumerate() {
+            payouts += outcome.total_shares as u128 * self.payout_per_share(i)? as u128;
+        }
+        let payouts = payouts / 10u128.pow(SHARES_DECIMALS);
+        u64::try_from(payouts).map_err(|_| error!(CustomError::Overflow))
+    }
+
+    /// Current price of every outcome, the payout numerators that refund holders of an invalid market
+    pub fn last_prices(&self) -> Result<Vec<u64>> {
+        let curve = self.pricing_curve();
+        let q = self.outcome_shares();
+        (0..q.len()).map(|i| curve.price(&q, i)).collect()
+    }
+
+    /// Index of the outcome whose shares are minted by `mint`
+    pub fn outcome_index(&self, mint: &Pubkey) -> Result<usize> {
+        self.outcomes
+            .iter()
+            .position(|o| o.mint == *mint)
+            .ok_or(error!(CustomError::InvalidMint))
+    }
+
+    /// Validates and stores one payout numerator per outcome.
+    /// The denominator is their sum, so payouts never exceed `PAYOUT_PER_SHARE`.
+    fn record_payouts(&mut self, payout_numerators: Vec<u64>) -> Result<()> {
+        require!(
+            payout_numerators.len() == self.outcomes.len(),
+            CustomError::InvalidPayouts
+        );
+
+        let payout_denominator = payout_numerators
+            .iter()
+            .try_fold(0u64, |sum, &n| sum.checked_add(n))
+            .ok_or(CustomError::Overflow)?;
+        require!(payout_denominator > 0, CustomError::InvalidPayouts);
+
+        self.payout_numerators = payout_numerators;
+        self.payout_denominator = payout_denominator;
+        Ok(())
+    }
+
+    /// Records a pending result that becomes final once the challenge period passes undisputed
+    pub fn propose_payouts(&mut self, payout_numerators: Vec<u64>, now: i64) -> Result<()> {
+        require!(
+            self.resolution_mode == ResolutionMode::Oracle,
+            CustomError::InvalidResolutionMode
+        );
+        require!(!self.market_settled, CustomError::MarketAlreadySettled);
+        require!(!self.outcome_proposed, CustomError::OutcomeAlreadyProposed);
+
+        self.record_payouts(payout_numerators)?;
+        self.outcome_proposed = true;
+        self.outcome_proposed_at = now;
+        Ok(())
+    }
+
+    /// Settles the market immediately, used for rulings and optimistic settlement
+    pub fn settle(&mut self, payout_numerators: Vec<u64>) -> Result<()> {
+        require!(!self.market_settled, CustomError::MarketAlreadySettled);
+
+        self.record_payouts(payout_numerators)?;
+        self.market_settled = true;
+        Ok(())
+    }
+
+    /// Whether a proposed result can still be disputed at `now`
+    pub fn challenge_window_open(&self, now: i64) -> bool {
+        self.outcome_proposed
+            && !self.market_settled
+            && now < self.outcome_proposed_at.saturating_add(self.challenge_period)
+    }
+
+    /// Settles an undisputed proposal once its challenge period has passed
+    pub fn finalize_if_ready(&mut self, now: i64) {
+        if self.outcome_proposed
+            && !self.outcome_disputed
+            && !self.market_settled
+            && !self.challenge_window_open(now)
+        {
+            self.market_settled = true;
+        }
+    }
+
+    /// Whether `payout_numerators` pays out in the same proportions as the recorded result
+    pub fn payouts_match(&self, payout_numerators: &[u64]) -> bool {
+        let payout_denominator: u128 = payout_numerators.iter().map(|&n| n as u128).sum();
+        payout_numerators.len() == self.payout_numerators.len()
+            && payout_numerators
+                .iter()
+                .zip(self.payout_numerators.iter())
+                .all(|(&a, &b)| {
+                    a as u128 * self.payout_denominator as u128 == b as u128 * payout_denominator
+                })
+    }
+
+    /// Amount paid per whole share of `outcome_index` once the market is settled
+    pub fn payout_per_share(&self, outcome_index: usize) -> Result<u64> {
+        require!(self.market_settled, CustomError::MarketNotSettled);
+        require!(outcome_index < self.payout_numerators.len(), CustomError::InvalidOutcome);
+
+        let payout = (PAYOUT_PER_SHARE as u128)
+            .checked_mul(self.payout_numerators[outcome_index] as u128)
+            .and_then(|p| p.checked_div(self.payout_denominator as u128))
+            .ok_or(CustomError::MathError)?;
+        Ok(payout as u64)
+    }
+}
@@ -1,2 +1,8 @@
+pub mod access;
+pub mod config;
 pub mod market; 
-pub mod outcome;
\ No newline at end of file
+pub mod optimistic_resolution;
+pub mod outcome;
+pub mod position;
+pub mod referrer;
+pub mod resolver_committee;
\ No newline at end of file
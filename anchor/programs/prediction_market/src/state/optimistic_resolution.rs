@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Optimistic-oracle proposal for a market, escrowing both bonds in its own token account
+#[account]
+#[derive(InitSpace)]
+pub struct OptimisticResolution {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    #[max_len(10)]
+    pub payout_numerators: Vec<u64>,
+    pub proposed_at: i64,
+    pub bond: u64,
+    pub disputed: bool,
+    pub disputer: Pubkey,
+    pub resolved: bool,
+    pub bump: u8,
+}
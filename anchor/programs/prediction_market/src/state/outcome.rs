@@ -6,5 +6,7 @@ pub struct Outcome {
     #[max_len(50)]
     pub name: String,
     pub total_shares: u64,
+    /// Shares that only move prices, added when liquidity is rescaled
+    pub virtual_shares: u64,
     pub mint: Pubkey,
 }
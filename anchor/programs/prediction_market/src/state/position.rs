@@ -1,10 +1,13 @@
-
 use anchor_lang::prelude::*;
 
+/// Shares of one outcome a user has bought net of sales, the balance position limits check.
+/// Shares moved to another account still count against the buyer.
 #[account]
+#[derive(InitSpace)]
 pub struct Position {
     pub market: Pubkey,
     pub user: Pubkey,
     pub outcome_index: u64,
     pub num_shares: u64,
+    pub bump: u8,
 }
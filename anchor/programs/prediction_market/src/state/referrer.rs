@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Referral fees earned by `referrer` on one market
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerStats {
+    pub market: Pubkey,
+    pub referrer: Pubkey,
+    /// Fees owed to the referrer, held in the market's token account
+    pub accrued_fees: u64,
+    /// Fees earned over the lifetime of the referral
+    pub total_earned: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// M-of-N committee that resolves markets in `ResolutionMode::Committee`
+#[account]
+#[derive(InitSpace)]
+pub struct ResolverCommittee {
+    pub authority: Pubkey,
+    pub committee_id: u64,
+    #[max_len(10)]
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+/// A committee member's current vote on a market
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionVote {
+    pub market: Pubkey,
+    pub member: Pubkey,
+    /// Voted outcome, `outcomes.len()` for a vote to invalidate the market
+    pub outcome_index: u64,
+    pub has_voted: bool,
+    pub bump: u8,
+}
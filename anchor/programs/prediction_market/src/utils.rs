@@ -4,11 +4,15 @@ use anchor_lang::prelude::*;
 use crate::error::CustomError;
 use crate::constants::TOKEN_DECIMALS;
 use crate::constants::SHARES_DECIMALS;
+use crate::constants::SCALE;
+use crate::constants::MAX_FEE_BPS;
+use crate::constants::PAYOUT_PER_SHARE;
+use crate::state::market::Market;
 
 // Calculates the LMSR cost function
 /// `q` is a vector of shares for each outcome.
 /// `b` is the liquidity parameter.
-pub fn calculate_cost(q: &Vec<u64>, b: u64) -> Result<u64> {
+pub fn calculate_cost(q: &[u64], b: u64) -> Result<u64> {
     use std::f64;
 
     // Convert b to f64 for calculations
@@ -31,38 +35,237 @@ pub fn calculate_cost(q: &Vec<u64>, b: u64) -> Result<u64> {
     // Take the natural logarithm and scale back
     let cost: f64 = b_f64 * sum_exp.ln();
 
-    let scale_factor = 10u64.pow((TOKEN_DECIMALS - SHARES_DECIMALS) as u32) as f64;
+    let scale_factor = 10u64.pow(TOKEN_DECIMALS - SHARES_DECIMALS) as f64;
     let scaled_cost = cost * scale_factor;
 
     // Convert back to u64
     Ok(scaled_cost.round() as u64) // Round to nearest integer
 }
 
+/// Calculates the LMSR marginal price of `outcome_index`, scaled by `SCALE`.
+/// `p_i = e^(q_i / b) / Σ e^(q_j / b)`
+pub fn calculate_price(q: &[u64], b: u64, outcome_index: usize) -> Result<u64> {
+    require!(outcome_index < q.len(), CustomError::InvalidOutcome);
 
+    let b_f64 = b as f64;
+    let max_exp = q.iter().map(|&qi| qi as f64 / b_f64).fold(0.0, f64::max);
+    let exponentials: Vec<f64> = q.iter().map(|&qi| (qi as f64 / b_f64 - max_exp).exp()).collect();
+    let sum_exp: f64 = exponentials.iter().sum();
+    if sum_exp <= 0.0 {
+        return Err(error!(CustomError::MathError));
+    }
+
+    let price = exponentials[outcome_index] / sum_exp;
+    Ok((price * SCALE as f64).round() as u64)
+}
+
+/// Effective liquidity parameter of an LS-LMSR market, `b(q) = α · Σ q_i`.
+/// `b` is used as a floor so the market is defined before any shares exist.
+/// `alpha` is scaled by `SCALE`.
+pub fn calculate_ls_lmsr_b(q: &[u64], b: u64, alpha: u64) -> f64 {
+    let total_shares: f64 = q.iter().map(|&qi| qi as f64).sum();
+    let alpha_f64 = alpha as f64 / SCALE as f64;
+
+    (alpha_f64 * total_shares).max(b as f64)
+}
+
+/// Calculates the LS-LMSR cost function (Othman et al.)
+/// `C(q) = b(q) * ln(Σ e^(q_i / b(q)))`
+pub fn calculate_ls_lmsr_cost(q: &[u64], b: u64, alpha: u64) -> Result<u64> {
+    let b_q = calculate_ls_lmsr_b(q, b, alpha);
+    if b_q <= 0.0 {
+        return Err(error!(CustomError::InvalidB));
+    }
+
+    // Shift by the largest exponent so large q / b(q) ratios don't overflow
+    let max_exp = q.iter().map(|&qi| qi as f64 / b_q).fold(0.0, f64::max);
+    let sum_exp: f64 = q.iter().map(|&qi| (qi as f64 / b_q - max_exp).exp()).sum();
+    if sum_exp <= 0.0 {
+        return Err(error!(CustomError::MathError));
+    }
+
+    let cost: f64 = b_q * (max_exp + sum_exp.ln());
 
+    let scale_factor = 10u64.pow(TOKEN_DECIMALS - SHARES_DECIMALS) as f64;
+    let scaled_cost = cost * scale_factor;
+
+    Ok(scaled_cost.round() as u64)
+}
+
+/// Calculates the LS-LMSR marginal price of `outcome_index`, scaled by `SCALE`.
+/// Prices sum to slightly more than one, which is the market maker's built-in spread.
+pub fn calculate_ls_lmsr_price(q: &[u64], b: u64, alpha: u64, outcome_index: usize) -> Result<u64> {
+    require!(outcome_index < q.len(), CustomError::InvalidOutcome);
+
+    let b_q = calculate_ls_lmsr_b(q, b, alpha);
+    if b_q <= 0.0 {
+        return Err(error!(CustomError::InvalidB));
+    }
+
+    let max_exp = q.iter().map(|&qi| qi as f64 / b_q).fold(0.0, f64::max);
+    let exponentials: Vec<f64> = q.iter().map(|&qi| (qi as f64 / b_q - max_exp).exp()).collect();
+    let sum_exp: f64 = exponentials.iter().sum();
+    if sum_exp <= 0.0 {
+        return Err(error!(CustomError::MathError));
+    }
+
+    let softmax = exponentials[outcome_index] / sum_exp;
+    let total_shares: f64 = q.iter().map(|&qi| qi as f64).sum();
+    let alpha_f64 = alpha as f64 / SCALE as f64;
+
+    // While the floor on b is active the curve is plain LMSR
+    let price = if alpha_f64 * total_shares <= b as f64 {
+        softmax
+    } else {
+        let weighted_shares: f64 = q
+            .iter()
+            .zip(exponentials.iter())
+            .map(|(&qi, &ei)| qi as f64 * ei)
+            .sum::<f64>()
+            / sum_exp;
+        alpha_f64 * (max_exp + sum_exp.ln()) + softmax - weighted_shares / total_shares
+    };
+
+    Ok((price * SCALE as f64).round() as u64)
+}
+
+/// Integer square root of `n`, rounded up
+pub fn ceil_sqrt(n: u128) -> Result<u128> {
+    if n < 2 {
+        return Ok(n);
+    }
+
+    // Newton's method from a power of two above the root
+    let mut x: u128 = 1 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+
+    let square = x.checked_mul(x).ok_or(CustomError::Overflow)?;
+    Ok(if square == n { x } else { x + 1 })
+}
+
+/// Calculates the cost function equivalent to a binary constant-product pool.
+/// Backing the pool with `S` complete sets implies balances of `S - q_i` of each outcome,
+/// and `(S - q_0)(S - q_1) = b²` gives `S = (q_0 + q_1 + sqrt((q_0 - q_1)² + 4b²)) / 2`.
+/// Rounded up so the pool never ends below the invariant.
+pub fn calculate_cpmm_cost(q: &[u64], b: u64) -> Result<u64> {
+    require!(q.len() == 2, CustomError::InvalidCurveOutcomes);
+
+    let scale_factor = 10u128.pow(TOKEN_DECIMALS - SHARES_DECIMALS);
+    let q_0 = q[0] as u128 * scale_factor;
+    let q_1 = q[1] as u128 * scale_factor;
+    let liquidity = b as u128 * scale_factor;
+
+    let diff = q_0.abs_diff(q_1);
+    let discriminant = diff
+        .checked_mul(diff)
+        .and_then(|d| d.checked_add(liquidity.checked_mul(liquidity)?.checked_mul(4)?))
+        .ok_or(CustomError::Overflow)?;
+    let root = ceil_sqrt(discriminant)?;
+
+    let cost = (q_0 + q_1 + root).div_ceil(2);
+    u64::try_from(cost).map_err(|_| error!(CustomError::Overflow))
+}
+
+/// Calculates the constant-product marginal price of `outcome_index`, scaled by `SCALE`.
+/// `p_i = 1/2 + (q_i - q_j) / (2 * sqrt((q_i - q_j)² + 4b²))`
+pub fn calculate_cpmm_price(q: &[u64], b: u64, outcome_index: usize) -> Result<u64> {
+    require!(q.len() == 2, CustomError::InvalidCurveOutcomes);
+    require!(outcome_index < q.len(), CustomError::InvalidOutcome);
+
+    let diff = q[outcome_index] as f64 - q[1 - outcome_index] as f64;
+    let b_f64 = b as f64;
+    let price = 0.5 + diff / (2.0 * (diff * diff + 4.0 * b_f64 * b_f64).sqrt());
+
+    Ok((price * SCALE as f64).round() as u64)
+}
+
+/// Checks the constant-product invariant against the market's reserves.
+/// Every implied pool balance `reserves - q_i` must be positive and their product at least `b²`.
+pub fn check_cpmm_invariant(q: &[u64], b: u64, reserves: u64) -> Result<()> {
+    require!(q.len() == 2, CustomError::InvalidCurveOutcomes);
+
+    let scale_factor = 10u128.pow(TOKEN_DECIMALS - SHARES_DECIMALS);
+    let liquidity = b as u128 * scale_factor;
+
+    let mut product: u128 = 1;
+    for &qi in q {
+        let pool_balance = (reserves as u128)
+            .checked_sub(qi as u128 * scale_factor)
+            .filter(|&balance| balance > 0)
+            .ok_or(CustomError::InvariantViolated)?;
+        // An overflowing product is necessarily above the invariant
+        product = product.saturating_mul(pool_balance);
+    }
+
+    let invariant = liquidity.checked_mul(liquidity).ok_or(CustomError::Overflow)?;
+    require!(product >= invariant, CustomError::InvariantViolated);
+    Ok(())
+}
 
 /// Calculates the fee based on cost and fee percent
 pub fn calculate_fee(cost: u64, fee_percent: u64) -> Result<u64> {
     // fee_percent is expected to be in basis points (e.g., 500 for 5%)
-    let fee = (cost.checked_mul(fee_percent).ok_or(CustomError::Overflow)?)
-        .checked_div(10000)
+    require!(fee_percent <= MAX_FEE_BPS, CustomError::FeeTooHigh);
+    let fee = (cost as u128)
+        .checked_mul(fee_percent as u128)
+        .and_then(|f| f.checked_div(MAX_FEE_BPS as u128))
         .ok_or(CustomError::Overflow)?;
-    Ok(fee)
+    Ok(fee as u64)
+}
+
+/// Calculates the funds needed to cover the market maker's worst-case loss, `b * ln(n)`.
+/// This bound holds for both LMSR and LS-LMSR, whose worst case is the cost at `q = 0`.
+pub fn calculate_required_initial_funds(b: u64, num_outcomes: usize) -> Result<u64> {
+    require!(num_outcomes > 0, CustomError::NoOutcomes);
+
+    let required_funds = b as f64 * (num_outcomes as f64).ln();
+
+    let scale_factor = 10u64.pow(TOKEN_DECIMALS - SHARES_DECIMALS) as f64;
+    let scaled_funds = (required_funds * scale_factor).ceil(); // Round up to ensure adequacy
+
+    if !scaled_funds.is_finite() || scaled_funds < 0.0 || scaled_funds > u64::MAX as f64 {
+        return Err(error!(CustomError::MathError));
+    }
+    Ok(scaled_funds as u64)
+}
+
+/// Fails unless the market's vault covers every outstanding obligation: the worst-case
+/// payout (the exact payout once settled), the fee ledgers and a pending dispute bond.
+/// Runs at the end of every handler that moves vault tokens or rewrites the market's
+/// ledgers, fee or payouts; handlers that only touch flags, config or bond vaults skip it.
+pub fn assert_solvent(market: &Market, vault_amount: u64) -> Result<()> {
+    let share_liability: u128 = if market.market_settled {
+        market.outstanding_payouts()? as u128
+    } else {
+        let max_shares = market.outcomes.iter().map(|o| o.total_shares).max().unwrap_or(0);
+        max_shares as u128 * PAYOUT_PER_SHARE as u128 / 10u128.pow(SHARES_DECIMALS)
+    };
+
+    let pending_bond = if market.outcome_disputed && !market.market_settled {
+        market.dispute_bond
+    } else {
+        0
+    };
+    let liabilities = share_liability
+        + market.collected_fees as u128
+        + market.protocol_fees as u128
+        + market.referral_fees as u128
+        + pending_bond as u128;
+
+    if (vault_amount as u128) < liabilities {
+        msg!(
+            "Market {} insolvent: vault {} < liabilities {}",
+            market.market_id,
+            vault_amount,
+            liabilities
+        );
+        return Err(error!(CustomError::Insolvent));
+    }
+    Ok(())
 }
-// pub fn calculate_required_initial_funds(b: u64, num_outcomes: usize) -> Result<u64, &'static str> {
-//     if num_outcomes == 0 {
-//         return 0; //msg!("Number of outcomes must be greater than 0");
-//     }
-
-//     let b_float = b as f64;
-//     let n_float = num_outcomes as f64;
-
-//     let required_funds = b_float * n_float.ln();
-
-//     // Safely cast the result back to `u64` if valid
-//     if required_funds.is_finite() && required_funds > 0.0 {
-//         Ok(required_funds.ceil() as u64) // Round up to ensure adequacy
-//     } else {
-//         CustomError//Err("Invalid calculation for initial funds")
-//     }
-// }
@@ -0,0 +1,162 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  ComputeBudgetProgram, Ed25519Program, Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction,
+} from '@solana/web3.js';
+import { TestEnv, TestMarket, buyShares, createMarket, currentTime, fundedWallet, setupTestEnv } from './helpers';
+
+describe("Gated markets", () => {
+  let env: TestEnv;
+  let authority: Keypair;
+  let buyer: Keypair;
+
+  const gatedMarket = () => createMarket(env, { accessAuthority: authority.publicKey });
+
+  const allowanceAddress = (m: TestMarket, user: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("allowance"), m.market.toBuffer(), user.toBuffer()],
+      env.program.programId
+    )[0];
+
+  const permitNonceAddress = (m: TestMarket, user: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("permit"), m.market.toBuffer(), user.toBuffer()],
+      env.program.programId
+    )[0];
+
+  // ed25519 verification of `market || user || expires_at || nonce`, signed by `signer`
+  const permit = (
+    market: PublicKey, user: PublicKey, expiresAt: bigint, nonce = BigInt(0), signer = authority
+  ): TransactionInstruction => {
+    const expiryAndNonce = Buffer.alloc(16);
+    expiryAndNonce.writeBigInt64LE(expiresAt, 0);
+    expiryAndNonce.writeBigUInt64LE(nonce, 8);
+    return Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: Buffer.concat([market.toBuffer(), user.toBuffer(), expiryAndNonce]),
+    });
+  };
+
+  const buyWithPermit = (m: TestMarket, numShares: number, preInstructions: TransactionInstruction[]) =>
+    buyShares(env, m, buyer, 0, numShares, {
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      permitNonce: permitNonceAddress(m, buyer.publicKey),
+    }, preInstructions);
+
+  const revoke = (m: TestMarket, user: PublicKey) =>
+    env.program.methods
+      .revokeAllowance()
+      .accounts({
+        market: m.market,
+        allowance: allowanceAddress(m, user),
+        permitNonce: permitNonceAddress(m, user),
+        authority: authority.publicKey,
+      } as any)
+      .signers([authority])
+      .rpc();
+
+  const grant = (m: TestMarket, user: PublicKey) =>
+    env.program.methods
+      .grantAllowance(user)
+      .accounts({ market: m.market, allowance: allowanceAddress(m, user), authority: authority.publicKey } as any)
+      .signers([authority])
+      .rpc();
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+    ({ wallet: authority } = await fundedWallet(env));
+    ({ wallet: buyer } = await fundedWallet(env, 1000));
+  });
+
+  it("Rejects a wallet without an allowance or permit", async () => {
+    const m = await gatedMarket();
+
+    await expect(buyShares(env, m, buyer, 0, 1)).rejects.toThrow("Wallet is not approved for this market");
+  });
+
+  it("Lets an approved wallet trade until the allowance is revoked", async () => {
+    const m = await gatedMarket();
+    const allowance = allowanceAddress(m, buyer.publicKey);
+
+    await grant(m, buyer.publicKey);
+    await buyShares(env, m, buyer, 0, 2, { allowance });
+
+    await revoke(m, buyer.publicKey);
+    expect(await env.provider.connection.getAccountInfo(allowance)).toBeNull();
+
+    await expect(buyShares(env, m, buyer, 0, 3)).rejects.toThrow("Wallet is not approved for this market");
+  });
+
+  it("Accepts a permit verified in the preceding instruction", async () => {
+    const m = await gatedMarket();
+    const expiresAt = (await currentTime(env)) + BigInt(600);
+
+    await buyWithPermit(m, 1, [permit(m.market, buyer.publicKey, expiresAt)]);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.outcomes[0].totalShares.toNumber()).toBe(1);
+  });
+
+  it("Accepts each permit only once", async () => {
+    const m = await gatedMarket();
+    const expiresAt = (await currentTime(env)) + BigInt(600);
+    const first = permit(m.market, buyer.publicKey, expiresAt);
+
+    await buyWithPermit(m, 1, [first]);
+    await expect(buyWithPermit(m, 2, [first])).rejects.toThrow("Permit was already used or revoked");
+
+    // The next permit carries the next nonce
+    await buyWithPermit(m, 3, [permit(m.market, buyer.publicKey, expiresAt, BigInt(1))]);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.outcomes[0].totalShares.toNumber()).toBe(4);
+  });
+
+  it("Rejects permits issued before the user's access was revoked", async () => {
+    const m = await gatedMarket();
+    const expiresAt = (await currentTime(env)) + BigInt(600);
+    const issued = permit(m.market, buyer.publicKey, expiresAt);
+
+    await grant(m, buyer.publicKey);
+    await revoke(m, buyer.publicKey);
+
+    await expect(buyWithPermit(m, 1, [issued])).rejects.toThrow("Permit was already used or revoked");
+  });
+
+  it("Rejects expired, misdirected or misplaced permits", async () => {
+    const m = await gatedMarket();
+    const otherMarket = await gatedMarket();
+    const now = await currentTime(env);
+    const expiresAt = now + BigInt(600);
+
+    // Expired
+    await expect(
+      buyWithPermit(m, 1, [permit(m.market, buyer.publicKey, now - BigInt(1))])
+    ).rejects.toThrow("Permit is missing, malformed or expired");
+
+    // Issued for another market
+    await expect(
+      buyWithPermit(m, 2, [permit(otherMarket.market, buyer.publicKey, expiresAt)])
+    ).rejects.toThrow("Permit is missing, malformed or expired");
+
+    // Issued for another user
+    await expect(
+      buyWithPermit(m, 3, [permit(m.market, env.user.publicKey, expiresAt)])
+    ).rejects.toThrow("Permit is missing, malformed or expired");
+
+    // Signed by someone other than the access authority
+    await expect(
+      buyWithPermit(m, 4, [permit(m.market, buyer.publicKey, expiresAt, BigInt(0), buyer)])
+    ).rejects.toThrow("Permit is missing, malformed or expired");
+
+    // Not the instruction right before the trade
+    await expect(
+      buyWithPermit(m, 5, [
+        permit(m.market, buyer.publicKey, expiresAt),
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ])
+    ).rejects.toThrow("Permit is missing, malformed or expired");
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.outcomes[0].totalShares.toNumber()).toBe(0);
+  });
+});
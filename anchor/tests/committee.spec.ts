@@ -0,0 +1,126 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import {
+  TOKEN, TestEnv, TestMarket, buyShares, claimPayout, closeMarket, createMarket, fundedWallet, setupTestEnv,
+} from './helpers';
+
+describe("Resolver committees", () => {
+  let env: TestEnv;
+  let trader: Keypair;
+  let members: Keypair[];
+  let outsider: Keypair;
+  let committee: PublicKey;
+
+  const committeeAddress = (committeeId: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("committee"), env.user.publicKey.toBuffer(), new anchor.BN(committeeId).toArrayLike(Buffer, "le", 8)],
+      env.program.programId
+    )[0];
+
+  const createCommittee = (committeeId: number, memberKeys: PublicKey[], threshold: number) =>
+    env.program.methods
+      .createCommittee(new anchor.BN(committeeId), memberKeys, threshold)
+      .accounts({ committee: committeeAddress(committeeId), authority: env.user.publicKey } as any)
+      .signers([env.user])
+      .rpc();
+
+  const committeeMarket = () => createMarket(env, {
+    resolutionMode: { committee: {} },
+    oracle: committee,
+    committee,
+  });
+
+  const vote = (m: TestMarket, member: Keypair, outcomeIndex: number) => {
+    const [votePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote"), m.market.toBuffer(), member.publicKey.toBuffer()],
+      env.program.programId
+    );
+    return env.program.methods
+      .submitVote(new anchor.BN(outcomeIndex))
+      .accounts({ market: m.market, committee, vote: votePDA, member: member.publicKey } as any)
+      .signers([member])
+      .rpc();
+  };
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+    ({ wallet: trader } = await fundedWallet(env, 1000));
+    members = [];
+    for (let i = 0; i < 3; i++) {
+      members.push((await fundedWallet(env)).wallet);
+    }
+    ({ wallet: outsider } = await fundedWallet(env));
+
+    // 2-of-3 committee
+    await createCommittee(10, members.map((m) => m.publicKey), 2);
+    committee = committeeAddress(10);
+  });
+
+  it("Rejects duplicate members and thresholds outside the member count", async () => {
+    const [a, b, c] = members.map((m) => m.publicKey);
+    const message = "Committee needs unique members and a threshold between one and the member count";
+
+    await expect(createCommittee(1, [a, a, b], 2)).rejects.toThrow(message);
+    await expect(createCommittee(2, [a, b, c], 0)).rejects.toThrow(message);
+    await expect(createCommittee(3, [a, b, c], 4)).rejects.toThrow(message);
+  });
+
+  it("Rejects a committee market whose oracle is not the committee", async () => {
+    const message = "Committee markets need the resolver committee as their oracle";
+
+    await expect(
+      createMarket(env, { resolutionMode: { committee: {} }, oracle: env.user.publicKey, committee })
+    ).rejects.toThrow(message);
+    await expect(
+      createMarket(env, { resolutionMode: { committee: {} }, oracle: committee })
+    ).rejects.toThrow(message);
+  });
+
+  it("Settles once the threshold agrees, counting changed votes", async () => {
+    const m = await committeeMarket();
+    await buyShares(env, m, env.user, 0, 10);
+    await buyShares(env, m, trader, 1, 10);
+
+    await expect(vote(m, members[2], 0)).rejects.toThrow("Market not closed yet");
+    await closeMarket(env, m);
+
+    await expect(vote(m, outsider, 0)).rejects.toThrow("Signer is not a committee member");
+
+    await vote(m, members[0], 1);
+    await vote(m, members[1], 0);
+    let market = await env.program.account.market.fetch(m.market);
+    expect(market.committeeVotes).toEqual([1, 1, 0]);
+    expect(market.marketSettled).toBe(false);
+
+    // The first member comes around, giving outcome 0 two of three votes
+    await vote(m, members[0], 0);
+    market = await env.program.account.market.fetch(m.market);
+    expect(market.committeeVotes).toEqual([2, 0, 0]);
+    expect(market.marketSettled).toBe(true);
+    expect(market.winningOutcome.toNumber()).toBe(0);
+
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+    expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(0));
+  });
+
+  it("Invalidates by vote and refunds at the last prices", async () => {
+    const m = await committeeMarket();
+    await buyShares(env, m, env.user, 0, 30);
+    await buyShares(env, m, trader, 1, 10);
+
+    // The index after the last outcome votes to invalidate, even while trading is open
+    await vote(m, members[0], 2);
+    await vote(m, members[2], 2);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketInvalid).toBe(true);
+    expect(market.marketClosed).toBe(true);
+    expect(market.marketSettled).toBe(true);
+
+    const numerators = market.payoutNumerators.map((n) => BigInt(n.toString()));
+    const denominator = BigInt(market.payoutDenominator.toString());
+    expect(numerators[0]).toBeGreaterThan(numerators[1]);
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(30) * ((BigInt(TOKEN) * numerators[0]) / denominator));
+    expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(10) * ((BigInt(TOKEN) * numerators[1]) / denominator));
+  });
+});
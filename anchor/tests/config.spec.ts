@@ -0,0 +1,59 @@
+import * as anchor from '@coral-xyz/anchor';
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Program } from '@coral-xyz/anchor';
+import { SystemProgram, Keypair, PublicKey } from '@solana/web3.js';
+import { PredictionMarket } from '../target/types/prediction_market';
+import { marketAddress, programDataAddress, upgradeableProgramAccounts } from './helpers';
+
+const IDL = require('../target/idl/prediction_market.json');
+
+describe("Protocol config", () => {
+  let program: Program<PredictionMarket>;
+  let upgradeAuthority: Keypair;
+  let intruder: Keypair;
+
+  const fundedAccount = (address: PublicKey) => ({
+    address,
+    info: {
+      lamports: 10_000_000_000,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    },
+  });
+
+  const initializeConfig = (admin: Keypair, allowedMints: PublicKey[] = []) =>
+    program.methods
+      .initializeConfig(new anchor.BN(0), admin.publicKey, new anchor.BN(1000), allowedMints)
+      .accounts({ admin: admin.publicKey, program: marketAddress, programData: programDataAddress } as any)
+      .signers([admin])
+      .rpc();
+
+  beforeAll(async () => {
+    upgradeAuthority = Keypair.generate();
+    intruder = Keypair.generate();
+
+    const context = await startAnchor("", [], [
+      ...upgradeableProgramAccounts(upgradeAuthority.publicKey),
+      fundedAccount(upgradeAuthority.publicKey),
+      fundedAccount(intruder.publicKey),
+    ]);
+    program = new Program<PredictionMarket>(IDL, new BankrunProvider(context));
+  });
+
+  it("Rejects more allowed mints than the config holds", async () => {
+    const mints = Array.from({ length: 11 }, () => Keypair.generate().publicKey);
+
+    await expect(initializeConfig(upgradeAuthority, mints)).rejects.toThrow("Allowed mints must be unique and at most ten");
+  });
+
+  it("Only lets the upgrade authority initialize the config", async () => {
+    await expect(initializeConfig(intruder)).rejects.toThrow("Unauthorized");
+
+    await initializeConfig(upgradeAuthority);
+
+    const [config] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+    const configAccount = await program.account.config.fetch(config);
+    expect(configAccount.admin.toBase58()).toBe(upgradeAuthority.publicKey.toBase58());
+  });
+});
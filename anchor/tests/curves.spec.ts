@@ -0,0 +1,104 @@
+import { SCALE, TOKEN, TestEnv, buyShares, createMarket, sellShares, setupTestEnv, tokenBalance } from './helpers';
+
+// Mirrors calculate_ls_lmsr_cost: C(q) = b(q) * ln(Σ e^(q_i / b(q))) with b(q) = max(b, alpha * Σ q_i).
+// With alpha = 0 this is plain LMSR at a fixed b.
+function lsLmsrCost(q: number[], b: number, alpha: number): number {
+  const totalShares = q.reduce((sum, qi) => sum + qi, 0);
+  const bq = Math.max((alpha / SCALE) * totalShares, b);
+  const maxExp = Math.max(0, ...q.map((qi) => qi / bq));
+  const sumExp = q.reduce((sum, qi) => sum + Math.exp(qi / bq - maxExp), 0);
+  return Math.round(bq * (maxExp + Math.log(sumExp)) * TOKEN);
+}
+
+describe("Pricing curves", () => {
+  let env: TestEnv;
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+  });
+
+  describe("LS-LMSR", () => {
+    it("Rejects alpha outside (0, 1)", async () => {
+      await expect(
+        createMarket(env, { curve: { lsLmsr: {} }, alpha: 0 })
+      ).rejects.toThrow("LS-LMSR alpha must be between zero and one");
+
+      await expect(
+        createMarket(env, { curve: { lsLmsr: {} }, alpha: SCALE })
+      ).rejects.toThrow("LS-LMSR alpha must be between zero and one");
+    });
+
+    it("Prices trades with a b that grows with the outstanding shares", async () => {
+      const b = 100;
+      const alpha = SCALE / 2;
+      const m = await createMarket(env, { curve: { lsLmsr: {} }, alpha, b, feePercent: 0 });
+
+      // 400 shares lift b(q) from its floor of 100 to 200
+      const balanceBefore = await tokenBalance(env, env.userTokenAccount);
+      await buyShares(env, m, env.user, 0, 400);
+      const paid = Number(balanceBefore - (await tokenBalance(env, env.userTokenAccount)));
+
+      const expectedCost = lsLmsrCost([400, 0], b, alpha) - lsLmsrCost([0, 0], b, alpha);
+      expect(Math.abs(paid - expectedCost)).toBeLessThanOrEqual(1);
+      // A fixed b of 100 would have charged less
+      const fixedBCost = lsLmsrCost([400, 0], b, 0) - lsLmsrCost([0, 0], b, 0);
+      expect(paid).toBeGreaterThan(fixedBCost);
+
+      // Selling half brings Σq back to 200, where b(q) meets the floor again
+      const balanceBeforeSell = await tokenBalance(env, env.userTokenAccount);
+      await sellShares(env, m, env.user, 0, 200);
+      const refunded = Number((await tokenBalance(env, env.userTokenAccount)) - balanceBeforeSell);
+
+      const expectedRefund = lsLmsrCost([400, 0], b, alpha) - lsLmsrCost([200, 0], b, alpha);
+      expect(Math.abs(refunded - expectedRefund)).toBeLessThanOrEqual(1);
+
+      const market = await env.program.account.market.fetch(m.market);
+      expect(market.outcomes[0].totalShares.toNumber()).toBe(200);
+      expect(market.b.toNumber()).toBe(b);
+    });
+  });
+
+  describe("Constant product", () => {
+    it("Rejects markets without exactly two outcomes", async () => {
+      await expect(
+        createMarket(env, { curve: { cpmm: {} }, outcomes: ["A", "B", "C"] })
+      ).rejects.toThrow("Constant-product markets require exactly two outcomes");
+    });
+
+    it("Refunds a buy in full when the same shares are sold back", async () => {
+      const m = await createMarket(env, { curve: { cpmm: {} }, feePercent: 0 });
+      const marketBefore = await env.program.account.market.fetch(m.market);
+
+      const balanceBefore = await tokenBalance(env, env.userTokenAccount);
+      await buyShares(env, m, env.user, 0, 50);
+      const balanceAfterBuy = await tokenBalance(env, env.userTokenAccount);
+      await sellShares(env, m, env.user, 0, 50);
+      const balanceAfterSell = await tokenBalance(env, env.userTokenAccount);
+
+      expect(balanceAfterBuy).toBeLessThan(balanceBefore);
+      expect(balanceAfterSell - balanceAfterBuy).toBe(balanceBefore - balanceAfterBuy);
+
+      const marketAfter = await env.program.account.market.fetch(m.market);
+      expect(marketAfter.outcomes[0].totalShares.toNumber()).toBe(0);
+      expect(marketAfter.marketMakerFunds.toString()).toBe(marketBefore.marketMakerFunds.toString());
+    });
+
+    it("Keeps the implied pool balances above the invariant", async () => {
+      const b = 100;
+      const m = await createMarket(env, { curve: { cpmm: {} }, b });
+      await buyShares(env, m, env.user, 0, 80);
+      await buyShares(env, m, env.user, 1, 30);
+
+      // The reserves back (S - q_0)(S - q_1) >= b², both balances positive
+      const market = await env.program.account.market.fetch(m.market);
+      const reserves = BigInt(market.marketMakerFunds.toString());
+      const balances = market.outcomes.map((o) => reserves - BigInt(o.totalShares.toString()) * BigInt(TOKEN));
+      const liquidity = BigInt(b) * BigInt(TOKEN);
+      expect(balances.every((balance) => balance > BigInt(0))).toBe(true);
+      expect(balances[0] * balances[1] >= liquidity * liquidity).toBe(true);
+
+      // Buying the outcome moved its price above one half
+      expect(balances[0]).toBeLessThan(balances[1]);
+    });
+  });
+});
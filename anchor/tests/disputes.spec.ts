@@ -0,0 +1,146 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as splToken from '@solana/spl-token';
+import {
+  TOKEN, TestEnv, TestMarket, buyShares, claimPayout, closeMarket, createMarket, currentTime,
+  fundedWallet, setupTestEnv, tokenBalance, warpTo,
+} from './helpers';
+
+const CHALLENGE_PERIOD = 600;
+const DISPUTE_BOND = 10 * TOKEN;
+
+describe("Disputes", () => {
+  let env: TestEnv;
+  let trader: Keypair;
+  let disputer: Keypair;
+  let disputerTokenAccount: PublicKey;
+  let arbitrator: Keypair;
+
+  // A closed market where the user holds 10 shares of outcome 0 and the trader 10 of outcome 1,
+  // with the oracle's proposal that outcome 0 won
+  const proposedMarket = async (): Promise<TestMarket> => {
+    const m = await createMarket(env, {
+      challengePeriod: CHALLENGE_PERIOD,
+      arbitrator: arbitrator.publicKey,
+      disputeBond: DISPUTE_BOND,
+    });
+    await buyShares(env, m, env.user, 0, 10);
+    await buyShares(env, m, trader, 1, 10);
+    await closeMarket(env, m);
+
+    await env.program.methods
+      .proposeOutcome(new anchor.BN(0))
+      .accounts({ market: m.market, oracle: env.user.publicKey })
+      .signers([env.user])
+      .rpc();
+    return m;
+  };
+
+  const dispute = (m: TestMarket) =>
+    env.program.methods
+      .disputeOutcome()
+      .accounts({
+        market: m.market,
+        marketTokenAccount: m.marketTokenAccount,
+        disputerTokenAccount,
+        disputer: disputer.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([disputer])
+      .rpc();
+
+  const resolveDispute = (m: TestMarket, payoutNumerators: number[]) =>
+    env.program.methods
+      .resolveDispute(payoutNumerators.map((n) => new anchor.BN(n)))
+      .accounts({
+        market: m.market,
+        marketTokenAccount: m.marketTokenAccount,
+        disputerTokenAccount,
+        arbitrator: arbitrator.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([arbitrator])
+      .rpc();
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+    ({ wallet: trader } = await fundedWallet(env, 1000));
+    ({ wallet: disputer, tokenAccount: disputerTokenAccount } = await fundedWallet(env, 1000));
+    ({ wallet: arbitrator } = await fundedWallet(env));
+  });
+
+  it("Rejects a challenge period without an arbitrator", async () => {
+    await expect(
+      createMarket(env, {
+        challengePeriod: CHALLENGE_PERIOD,
+        arbitrator: PublicKey.default,
+        disputeBond: DISPUTE_BOND,
+      })
+    ).rejects.toThrow("Challenge period must not be negative and disputes need a bond and an arbitrator");
+  });
+
+  it("Only pays out once the challenge window has passed", async () => {
+    const m = await proposedMarket();
+
+    await expect(claimPayout(env, m, trader, 1)).rejects.toThrow("Market is not settled yet");
+
+    const market = await env.program.account.market.fetch(m.market);
+    await warpTo(env, BigInt(market.outcomeProposedAt.add(new anchor.BN(CHALLENGE_PERIOD)).toString()));
+
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+  });
+
+  it("Treats set_outcome as a proposal open to disputes", async () => {
+    const m = await createMarket(env, {
+      challengePeriod: CHALLENGE_PERIOD,
+      arbitrator: arbitrator.publicKey,
+      disputeBond: DISPUTE_BOND,
+    });
+    await closeMarket(env, m);
+
+    await env.program.methods
+      .setOutcome(new anchor.BN(1))
+      .accounts({ market: m.market, oracle: env.user.publicKey })
+      .signers([env.user])
+      .rpc();
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketSettled).toBe(false);
+    expect(market.winningOutcome.toNumber()).toBe(1);
+    expect(market.outcomeProposedAt.toNumber()).toBeGreaterThan(0);
+  });
+
+  it("Forfeits the bond when the arbitrator upholds the proposal", async () => {
+    const m = await proposedMarket();
+    const disputerBalanceBefore = await tokenBalance(env, disputerTokenAccount);
+
+    await dispute(m);
+    expect(await tokenBalance(env, disputerTokenAccount)).toBe(disputerBalanceBefore - BigInt(DISPUTE_BOND));
+
+    // A disputed proposal never becomes final on its own
+    await warpTo(env, (await currentTime(env)) + BigInt(CHALLENGE_PERIOD));
+    await expect(claimPayout(env, m, trader, 1)).rejects.toThrow("Market is not settled yet");
+
+    const { collectedFees, protocolFees } = await env.program.account.market.fetch(m.market);
+    await resolveDispute(m, [1, 0]);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketSettled).toBe(true);
+    expect(market.protocolFees.sub(protocolFees).toNumber()).toBe(DISPUTE_BOND);
+    expect(market.collectedFees.eq(collectedFees)).toBe(true);
+    expect(await tokenBalance(env, disputerTokenAccount)).toBe(disputerBalanceBefore - BigInt(DISPUTE_BOND));
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+  });
+
+  it("Returns the bond when the arbitrator overturns the proposal", async () => {
+    const m = await proposedMarket();
+    const disputerBalanceBefore = await tokenBalance(env, disputerTokenAccount);
+
+    await dispute(m);
+    await resolveDispute(m, [0, 1]);
+
+    expect(await tokenBalance(env, disputerTokenAccount)).toBe(disputerBalanceBefore);
+    expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(10 * TOKEN));
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(0));
+  });
+});
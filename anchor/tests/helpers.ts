@@ -0,0 +1,372 @@
+import * as anchor from '@coral-xyz/anchor';
+import * as fs from 'fs';
+import * as path from 'path';
+import { AddedAccount, Clock, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Program } from '@coral-xyz/anchor';
+import { SystemProgram, Keypair, Transaction, PublicKey, TransactionInstruction } from '@solana/web3.js';
+import { PredictionMarket } from '../target/types/prediction_market';
+import * as splToken from '@solana/spl-token';
+
+const IDL = require('../target/idl/prediction_market.json');
+export const marketAddress = new PublicKey("AeHTqTh19seFqLVKvbrkEhgCJEzUWPJu2vxYppdoj6BL");
+export const TOKEN_DECIMALS = 9;
+// Base units per token, also the payout of one winning share
+export const TOKEN = Math.pow(10, TOKEN_DECIMALS);
+// Fixed-point scale of prices and alpha
+export const SCALE = 1_000_000;
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+export const programDataAddress = PublicKey.findProgramAddressSync(
+  [marketAddress.toBuffer()],
+  BPF_LOADER_UPGRADEABLE_ID
+)[0];
+
+// The program deployed through the upgradeable loader, so initialize_config can check its upgrade authority
+export function upgradeableProgramAccounts(upgradeAuthority: PublicKey): AddedAccount[] {
+  const elf = fs.readFileSync(path.join(__dirname, '../target/deploy/prediction_market.so'));
+
+  // UpgradeableLoaderState::Program { programdata_address }
+  const program = Buffer.alloc(36);
+  program.writeUInt32LE(2, 0);
+  programDataAddress.toBuffer().copy(program, 4);
+
+  // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(..) }, then the ELF
+  const programData = Buffer.alloc(45 + elf.length);
+  programData.writeUInt32LE(3, 0);
+  programData.writeUInt8(1, 12);
+  upgradeAuthority.toBuffer().copy(programData, 13);
+  elf.copy(programData, 45);
+
+  return [
+    {
+      address: marketAddress,
+      info: { lamports: 1_000_000_000, data: program, owner: BPF_LOADER_UPGRADEABLE_ID, executable: true },
+    },
+    {
+      address: programDataAddress,
+      info: { lamports: 100_000_000_000, data: programData, owner: BPF_LOADER_UPGRADEABLE_ID, executable: false },
+    },
+  ];
+}
+
+export interface TestEnv {
+  context: ProgramTestContext;
+  provider: BankrunProvider;
+  program: Program<PredictionMarket>;
+  // Config admin, treasury and creator of every test market
+  user: Keypair;
+  baseTokenMint: PublicKey;
+  userTokenAccount: PublicKey;
+}
+
+export interface TestMarket {
+  marketId: anchor.BN;
+  market: PublicKey;
+  marketTokenAccount: PublicKey;
+  outcomeMints: PublicKey[];
+}
+
+// Starts a bank with the program, a funded user, a base mint and a protocol config allowing it
+export async function setupTestEnv(): Promise<TestEnv> {
+  const user = Keypair.generate();
+  const baseTokenMint = Keypair.generate();
+
+  const context = await startAnchor("", [],
+    [
+      ...upgradeableProgramAccounts(user.publicKey),
+      {
+        address: user.publicKey,
+        info: {
+          lamports: 1_000_000_00000000,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      },
+    ]);
+
+  const provider = new BankrunProvider(context);
+  const program = new Program<PredictionMarket>(IDL, provider);
+
+  const userTokenAccount = await splToken.getAssociatedTokenAddress(baseTokenMint.publicKey, user.publicKey);
+  await provider.sendAndConfirm!(new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: user.publicKey,
+      newAccountPubkey: baseTokenMint.publicKey,
+      space: splToken.MINT_SIZE,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(splToken.MINT_SIZE),
+      programId: splToken.TOKEN_PROGRAM_ID,
+    }),
+    splToken.createInitializeMintInstruction(baseTokenMint.publicKey, TOKEN_DECIMALS, user.publicKey, null),
+    splToken.createAssociatedTokenAccountInstruction(user.publicKey, userTokenAccount, user.publicKey, baseTokenMint.publicKey),
+    splToken.createMintToInstruction(baseTokenMint.publicKey, userTokenAccount, user.publicKey, BigInt(1_000_000) * BigInt(TOKEN)),
+  ), [user, baseTokenMint]);
+
+  await program.methods
+    .initializeConfig(
+      new anchor.BN(0),               // protocol_fee_bps
+      user.publicKey,                 // treasury
+      new anchor.BN(1000),            // max_fee_bps
+      [baseTokenMint.publicKey]       // allowed_mints
+    )
+    .accounts({ admin: user.publicKey, program: marketAddress, programData: programDataAddress } as any)
+    .signers([user])
+    .rpc();
+
+  return { context, provider, program, user, baseTokenMint: baseTokenMint.publicKey, userTokenAccount };
+}
+
+// A new wallet with SOL for rent and `tokens` base tokens in its associated token account
+export async function fundedWallet(env: TestEnv, tokens = 0): Promise<{ wallet: Keypair; tokenAccount: PublicKey }> {
+  const wallet = Keypair.generate();
+  env.context.setAccount(wallet.publicKey, {
+    lamports: 10_000_000_000,
+    data: Buffer.alloc(0),
+    owner: SystemProgram.programId,
+    executable: false,
+  });
+
+  const tokenAccount = await splToken.getAssociatedTokenAddress(env.baseTokenMint, wallet.publicKey);
+  await env.provider.sendAndConfirm!(new Transaction().add(
+    splToken.createAssociatedTokenAccountInstruction(env.user.publicKey, tokenAccount, wallet.publicKey, env.baseTokenMint),
+    splToken.createMintToInstruction(env.baseTokenMint, tokenAccount, env.user.publicKey, BigInt(tokens) * BigInt(TOKEN)),
+  ), [env.user]);
+
+  return { wallet, tokenAccount };
+}
+
+let nextMarketId = 1;
+
+// Creates a market with the given overrides, every other setting is a plain binary LMSR market
+export async function createMarket(env: TestEnv, params: Record<string, any> = {}): Promise<TestMarket> {
+  const now = Number((await env.context.banksClient.getClock()).unixTimestamp);
+  const outcomes: string[] = params.outcomes ?? ["Yes", "No"];
+  const b: number = params.b ?? 100;
+  const curve = params.curve ?? { lmsr: {} };
+  // Cover the worst-case loss: b for the constant-product curve, b * ln(n) otherwise
+  const maxLoss = curve.cpmm ? b : b * Math.log(outcomes.length);
+  const initialFunds = params.initialFunds ?? Math.ceil(maxLoss + 1) * TOKEN;
+
+  const marketId = new anchor.BN(params.marketId ?? nextMarketId++);
+  const [market] = PublicKey.findProgramAddressSync(
+    [Buffer.from("market"), marketId.toArrayLike(Buffer, "le", 8)],
+    env.program.programId
+  );
+  const marketTokenAccount = await splToken.getAssociatedTokenAddress(env.baseTokenMint, market, true);
+
+  // Uninitialized outcome mints, initialized by create_market
+  const outcomeMints = outcomes.map(() => Keypair.generate());
+  const rent = await env.provider.connection.getMinimumBalanceForRentExemption(splToken.MINT_SIZE);
+  await env.provider.sendAndConfirm!(new Transaction().add(
+    ...outcomeMints.map((mint) => SystemProgram.createAccount({
+      fromPubkey: env.user.publicKey,
+      newAccountPubkey: mint.publicKey,
+      space: splToken.MINT_SIZE,
+      lamports: rent,
+      programId: splToken.TOKEN_PROGRAM_ID,
+    }))
+  ), [env.user, ...outcomeMints]);
+
+  await env.program.methods
+    .createMarket(
+      marketId,
+      params.title ?? "Test market",
+      outcomes,
+      params.oracle ?? env.user.publicKey,
+      new anchor.BN(b),
+      new anchor.BN(params.startTimestamp ?? now),
+      new anchor.BN(params.endTimestamp ?? now + 3600),
+      new anchor.BN(params.feePercent ?? 100),
+      params.feeRecipient ?? env.user.publicKey,
+      new anchor.BN(initialFunds.toString()),
+      curve,
+      new anchor.BN(params.alpha ?? 0),
+      params.marketType ?? { categorical: {} },
+      new anchor.BN(params.lowerBound ?? 0),
+      new anchor.BN(params.upperBound ?? 0),
+      new anchor.BN(params.challengePeriod ?? 0),
+      params.arbitrator ?? env.user.publicKey,
+      new anchor.BN(params.disputeBond ?? 0),
+      params.resolutionMode ?? { oracle: {} },
+      params.priceFeed ?? {
+        feed: PublicKey.default,
+        threshold: new anchor.BN(0),
+        comparison: { above: {} },
+        maxStaleness: new anchor.BN(0),
+        maxConfidenceBps: new anchor.BN(0),
+      },
+      new anchor.BN(params.lpFeeBps ?? 5000),
+      new anchor.BN(params.creatorFeeBps ?? 5000),
+      new anchor.BN(params.liquidityGrowthBps ?? 0),
+      new anchor.BN(params.maxB ?? 0),
+      new anchor.BN(params.maxSharesPerUser ?? 0),
+      new anchor.BN(params.maxTotalSharesPerOutcome ?? 0),
+      params.accessAuthority ?? PublicKey.default,
+      new anchor.BN(params.referralFeeBps ?? 0)
+    )
+    .accounts({
+      market,
+      user: env.user.publicKey,
+      baseTokenMint: env.baseTokenMint,
+      userTokenAccount: env.userTokenAccount,
+      marketTokenAccount,
+      committee: params.committee ?? null,
+      tokenProgram: splToken.TOKEN_PROGRAM_ID,
+    } as any)
+    .remainingAccounts(outcomeMints.map((mint) => ({ pubkey: mint.publicKey, isWritable: true, isSigner: false })))
+    .signers([env.user])
+    .rpc();
+
+  return { marketId, market, marketTokenAccount, outcomeMints: outcomeMints.map((mint) => mint.publicKey) };
+}
+
+// The owner's associated share account for `outcomeMint`, created if missing
+export async function shareAccount(env: TestEnv, outcomeMint: PublicKey, owner: PublicKey): Promise<PublicKey> {
+  const account = await splToken.getAssociatedTokenAddress(outcomeMint, owner);
+  // Bankrun rejects a repeated identical transaction, so only send the create once
+  if (!(await env.provider.connection.getAccountInfo(account))) {
+    await env.provider.sendAndConfirm!(new Transaction().add(
+      splToken.createAssociatedTokenAccountInstruction(env.user.publicKey, account, owner, outcomeMint)
+    ), [env.user]);
+  }
+  return account;
+}
+
+// Net shares of `outcomeIndex` bought by `user`, checked against the position limit
+export function positionAddress(env: TestEnv, m: TestMarket, user: PublicKey, outcomeIndex: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position"), m.market.toBuffer(), user.toBuffer(), new anchor.BN(outcomeIndex).toArrayLike(Buffer, "le", 8)],
+    env.program.programId
+  )[0];
+}
+
+export async function buyShares(
+  env: TestEnv,
+  m: TestMarket,
+  buyer: Keypair,
+  outcomeIndex: number,
+  numShares: number,
+  accounts: Record<string, any> = {},
+  preInstructions: TransactionInstruction[] = [],
+) {
+  return env.program.methods
+    .buyShares(new anchor.BN(outcomeIndex), new anchor.BN(numShares))
+    .accounts({
+      market: m.market,
+      buyerTokenAccount: await splToken.getAssociatedTokenAddress(env.baseTokenMint, buyer.publicKey),
+      marketTokenAccount: m.marketTokenAccount,
+      outcomeMint: m.outcomeMints[outcomeIndex],
+      buyerShareAccount: await shareAccount(env, m.outcomeMints[outcomeIndex], buyer.publicKey),
+      position: positionAddress(env, m, buyer.publicKey, outcomeIndex),
+      allowance: null,
+      instructionsSysvar: null,
+      permitNonce: null,
+      referrerStats: null,
+      baseTokenMint: env.baseTokenMint,
+      buyer: buyer.publicKey,
+      tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      ...accounts,
+    } as any)
+    .preInstructions(preInstructions)
+    .signers([buyer])
+    .rpc();
+}
+
+export async function sellShares(
+  env: TestEnv,
+  m: TestMarket,
+  seller: Keypair,
+  outcomeIndex: number,
+  numShares: number,
+  accounts: Record<string, any> = {},
+) {
+  return env.program.methods
+    .sellShares(new anchor.BN(outcomeIndex), new anchor.BN(numShares))
+    .accounts({
+      market: m.market,
+      buyerShareAccount: await splToken.getAssociatedTokenAddress(m.outcomeMints[outcomeIndex], seller.publicKey),
+      position: positionAddress(env, m, seller.publicKey, outcomeIndex),
+      allowance: null,
+      instructionsSysvar: null,
+      permitNonce: null,
+      referrerStats: null,
+      outcomeMint: m.outcomeMints[outcomeIndex],
+      sellerTokenAccount: await splToken.getAssociatedTokenAddress(env.baseTokenMint, seller.publicKey),
+      marketTokenAccount: m.marketTokenAccount,
+      seller: seller.publicKey,
+      tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      ...accounts,
+    } as any)
+    .signers([seller])
+    .rpc();
+}
+
+export async function tokenBalance(env: TestEnv, tokenAccount: PublicKey): Promise<bigint> {
+  return (await splToken.getAccount(env.provider.connection, tokenAccount)).amount;
+}
+
+// Every token in the market's vault is owed to the market maker or to a fee ledger
+export async function expectVaultMatchesLedgers(program: Program<PredictionMarket>, market: PublicKey, marketTokenAccount: PublicKey) {
+  const account = await program.account.market.fetch(market);
+  const vault = await splToken.getAccount(program.provider.connection, marketTokenAccount);
+  const ledgers = account.marketMakerFunds
+    .add(account.collectedFees)
+    .add(account.protocolFees)
+    .add(account.referralFees);
+  expect(vault.amount.toString()).toBe(ledgers.toString());
+}
+
+export async function currentTime(env: TestEnv): Promise<bigint> {
+  return (await env.context.banksClient.getClock()).unixTimestamp;
+}
+
+export async function warpTo(env: TestEnv, unixTimestamp: bigint) {
+  const clock = await env.context.banksClient.getClock();
+  env.context.setClock(
+    new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      unixTimestamp
+    )
+  );
+}
+
+// Moves past the market's end time and closes it as a keeper
+export async function closeMarket(env: TestEnv, m: TestMarket) {
+  const market = await env.program.account.market.fetch(m.market);
+  const endTimestamp = BigInt(market.endTimestamp.toString());
+  if ((await currentTime(env)) < endTimestamp) {
+    await warpTo(env, endTimestamp);
+  }
+
+  await env.program.methods
+    .closeMarket()
+    .accounts({ market: m.market, keeper: env.user.publicKey })
+    .signers([env.user])
+    .rpc();
+}
+
+// Claims the holder's shares of `outcomeIndex` and returns the payout
+export async function claimPayout(env: TestEnv, m: TestMarket, holder: Keypair, outcomeIndex: number): Promise<bigint> {
+  const holderTokenAccount = await splToken.getAssociatedTokenAddress(env.baseTokenMint, holder.publicKey);
+  const balanceBefore = await tokenBalance(env, holderTokenAccount);
+
+  await env.program.methods
+    .claimPayout()
+    .accounts({
+      market: m.market,
+      marketTokenAccount: m.marketTokenAccount,
+      userTokenAccount: holderTokenAccount,
+      outcomeMint: m.outcomeMints[outcomeIndex],
+      userShareAccount: await splToken.getAssociatedTokenAddress(m.outcomeMints[outcomeIndex], holder.publicKey),
+      user: holder.publicKey,
+      tokenProgram: splToken.TOKEN_PROGRAM_ID,
+    } as any)
+    .signers([holder])
+    .rpc();
+
+  return (await tokenBalance(env, holderTokenAccount)) - balanceBefore;
+}
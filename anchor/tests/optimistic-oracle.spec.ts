@@ -0,0 +1,168 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as splToken from '@solana/spl-token';
+import {
+  TOKEN, TestEnv, TestMarket, buyShares, claimPayout, closeMarket, createMarket, currentTime,
+  fundedWallet, setupTestEnv, tokenBalance, warpTo,
+} from './helpers';
+
+const CHALLENGE_PERIOD = 600;
+const BOND = 10 * TOKEN;
+
+describe("Optimistic oracle", () => {
+  let env: TestEnv;
+  let trader: Keypair;
+  let proposer: Keypair;
+  let proposerTokenAccount: PublicKey;
+  let disputer: Keypair;
+  let disputerTokenAccount: PublicKey;
+  let arbitrator: Keypair;
+
+  const resolutionAddress = (m: TestMarket) =>
+    PublicKey.findProgramAddressSync([Buffer.from("optimistic"), m.market.toBuffer()], env.program.programId)[0];
+
+  const bondVaultAddress = (m: TestMarket) =>
+    splToken.getAssociatedTokenAddressSync(env.baseTokenMint, resolutionAddress(m), true);
+
+  // A closed optimistic market where the user holds 10 shares of outcome 0 and the trader 10 of outcome 1
+  const closedMarket = async (): Promise<TestMarket> => {
+    const m = await createMarket(env, {
+      resolutionMode: { optimistic: {} },
+      challengePeriod: CHALLENGE_PERIOD,
+      disputeBond: BOND,
+      arbitrator: arbitrator.publicKey,
+    });
+    await buyShares(env, m, env.user, 0, 10);
+    await buyShares(env, m, trader, 1, 10);
+    await closeMarket(env, m);
+    return m;
+  };
+
+  const propose = (m: TestMarket, payoutNumerators: number[]) =>
+    env.program.methods
+      .proposeOptimisticOutcome(payoutNumerators.map((n) => new anchor.BN(n)))
+      .accounts({
+        market: m.market,
+        resolution: resolutionAddress(m),
+        baseTokenMint: env.baseTokenMint,
+        bondVault: bondVaultAddress(m),
+        proposerTokenAccount,
+        proposer: proposer.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([proposer])
+      .rpc();
+
+  const settle = (m: TestMarket) =>
+    env.program.methods
+      .settleOptimisticOutcome()
+      .accounts({
+        market: m.market,
+        resolution: resolutionAddress(m),
+        bondVault: bondVaultAddress(m),
+        proposerTokenAccount,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .rpc();
+
+  const dispute = (m: TestMarket) =>
+    env.program.methods
+      .disputeOptimisticOutcome()
+      .accounts({
+        market: m.market,
+        resolution: resolutionAddress(m),
+        bondVault: bondVaultAddress(m),
+        disputerTokenAccount,
+        disputer: disputer.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([disputer])
+      .rpc();
+
+  const resolve = (m: TestMarket, payoutNumerators: number[], winnerTokenAccount: PublicKey) =>
+    env.program.methods
+      .resolveOptimisticDispute(payoutNumerators.map((n) => new anchor.BN(n)))
+      .accounts({
+        market: m.market,
+        resolution: resolutionAddress(m),
+        bondVault: bondVaultAddress(m),
+        winnerTokenAccount,
+        arbitrator: arbitrator.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([arbitrator])
+      .rpc();
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+    ({ wallet: trader } = await fundedWallet(env, 1000));
+    ({ wallet: proposer, tokenAccount: proposerTokenAccount } = await fundedWallet(env, 1000));
+    ({ wallet: disputer, tokenAccount: disputerTokenAccount } = await fundedWallet(env, 1000));
+    ({ wallet: arbitrator } = await fundedWallet(env));
+  });
+
+  it("Rejects an optimistic market without an arbitrator", async () => {
+    await expect(
+      createMarket(env, {
+        resolutionMode: { optimistic: {} },
+        challengePeriod: CHALLENGE_PERIOD,
+        disputeBond: BOND,
+        arbitrator: PublicKey.default,
+      })
+    ).rejects.toThrow("Challenge period must not be negative and disputes need a bond and an arbitrator");
+  });
+
+  it("Settles an undisputed proposal and returns the bond", async () => {
+    const m = await closedMarket();
+    const proposerBalanceBefore = await tokenBalance(env, proposerTokenAccount);
+
+    await propose(m, [1, 0]);
+    expect(await tokenBalance(env, proposerTokenAccount)).toBe(proposerBalanceBefore - BigInt(BOND));
+    expect(await tokenBalance(env, bondVaultAddress(m))).toBe(BigInt(BOND));
+
+    const resolution = await env.program.account.optimisticResolution.fetch(resolutionAddress(m));
+    await warpTo(env, BigInt(resolution.proposedAt.add(new anchor.BN(CHALLENGE_PERIOD)).toString()));
+    await settle(m);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketSettled).toBe(true);
+    expect(await tokenBalance(env, proposerTokenAccount)).toBe(proposerBalanceBefore);
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+  });
+
+  it("Pays both bonds to the disputer when the arbitrator overturns the proposal", async () => {
+    const m = await closedMarket();
+    const proposerBalanceBefore = await tokenBalance(env, proposerTokenAccount);
+    const disputerBalanceBefore = await tokenBalance(env, disputerTokenAccount);
+
+    await propose(m, [1, 0]);
+    await expect(settle(m)).rejects.toThrow("Challenge period has not ended");
+
+    await dispute(m);
+    expect(await tokenBalance(env, bondVaultAddress(m))).toBe(BigInt(2 * BOND));
+
+    // Only the arbitrator can settle a disputed proposal, even after the window
+    await warpTo(env, (await currentTime(env)) + BigInt(CHALLENGE_PERIOD));
+    await resolve(m, [0, 1], disputerTokenAccount);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketSettled).toBe(true);
+    expect(await tokenBalance(env, bondVaultAddress(m))).toBe(BigInt(0));
+    expect(await tokenBalance(env, disputerTokenAccount)).toBe(disputerBalanceBefore + BigInt(BOND));
+    expect(await tokenBalance(env, proposerTokenAccount)).toBe(proposerBalanceBefore - BigInt(BOND));
+    expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(10 * TOKEN));
+  });
+
+  it("Pays both bonds to the proposer when the arbitrator upholds the proposal", async () => {
+    const m = await closedMarket();
+    const proposerBalanceBefore = await tokenBalance(env, proposerTokenAccount);
+
+    await propose(m, [1, 0]);
+    await dispute(m);
+    await expect(resolve(m, [1, 0], disputerTokenAccount)).rejects.toThrow("Invalid owner for the mint account.");
+    await resolve(m, [1, 0], proposerTokenAccount);
+
+    expect(await tokenBalance(env, proposerTokenAccount)).toBe(proposerBalanceBefore + BigInt(BOND));
+    expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+  });
+});
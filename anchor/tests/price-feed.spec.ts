@@ -0,0 +1,111 @@
+import * as anchor from '@coral-xyz/anchor';
+import { SystemProgram, Keypair, PublicKey } from '@solana/web3.js';
+import { TestEnv, TestMarket, createMarket, setupTestEnv, warpTo } from './helpers';
+
+// Writes a Pyth v2 price account with the fields read by resolve_from_feed
+function mockPriceFeedData(price: bigint, conf: bigint, publishTime: bigint): Buffer {
+  const data = Buffer.alloc(3312);
+  data.writeUInt32LE(0xa1b2c3d4, 0);   // magic
+  data.writeUInt32LE(2, 4);            // version
+  data.writeUInt32LE(3, 8);            // price account
+  data.writeInt32LE(-8, 20);           // exponent
+  data.writeBigInt64LE(publishTime, 96);
+  data.writeBigInt64LE(price, 208);    // aggregate price
+  data.writeBigUInt64LE(conf, 216);    // aggregate confidence
+  data.writeUInt32LE(1, 224);          // trading
+  return data;
+}
+
+const MAX_STALENESS = 60;
+
+describe("Price feed resolution", () => {
+  let env: TestEnv;
+  // Stands in for the Pyth program as the owner of mock price accounts
+  const feedProgram = Keypair.generate().publicKey;
+
+  // "SOL above $200 at expiry" with an 8-decimal feed, past its expiry
+  const feedMarket = async (): Promise<{ m: TestMarket; priceFeed: PublicKey; endTimestamp: bigint }> => {
+    const priceFeed = Keypair.generate().publicKey;
+    const m = await createMarket(env, {
+      resolutionMode: { priceFeed: {} },
+      priceFeed: {
+        feed: priceFeed,
+        threshold: new anchor.BN(200).mul(new anchor.BN(10).pow(new anchor.BN(8))),
+        comparison: { above: {} },
+        maxStaleness: new anchor.BN(MAX_STALENESS),
+        maxConfidenceBps: new anchor.BN(100),
+      },
+    });
+
+    const market = await env.program.account.market.fetch(m.market);
+    const endTimestamp = BigInt(market.endTimestamp.toString());
+    await warpTo(env, endTimestamp + BigInt(30));
+    return { m, priceFeed, endTimestamp };
+  };
+
+  const setFeed = (priceFeed: PublicKey, price: bigint, conf: bigint, publishTime: bigint, owner = feedProgram) => {
+    env.context.setAccount(priceFeed, {
+      lamports: 1_000_000_000,
+      data: mockPriceFeedData(price, conf, publishTime),
+      owner,
+      executable: false,
+    });
+  };
+
+  const resolveFromFeed = (m: TestMarket, priceFeed: PublicKey) =>
+    env.program.methods
+      .resolveFromFeed()
+      .accounts({ market: m.market, priceFeed } as any)
+      .rpc();
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+
+    await env.program.methods
+      .updateConfig(null, null, null, null, null, feedProgram)
+      .accounts({ admin: env.user.publicKey } as any)
+      .signers([env.user])
+      .rpc();
+  });
+
+  it("Rejects a price published before expiry", async () => {
+    const { m, priceFeed, endTimestamp } = await feedMarket();
+    setFeed(priceFeed, BigInt(210_00000000), BigInt(10000000), endTimestamp - BigInt(1));
+
+    await expect(resolveFromFeed(m, priceFeed)).rejects.toThrow("Price was published before the market ended or is stale");
+  });
+
+  it("Rejects a price published too long after expiry", async () => {
+    const { m, priceFeed, endTimestamp } = await feedMarket();
+    const publishTime = endTimestamp + BigInt(MAX_STALENESS + 1);
+    await warpTo(env, publishTime + BigInt(5));
+    setFeed(priceFeed, BigInt(210_00000000), BigInt(10000000), publishTime);
+
+    await expect(resolveFromFeed(m, priceFeed)).rejects.toThrow("Price was published before the market ended or is stale");
+  });
+
+  it("Rejects a price account not owned by the feed program", async () => {
+    const { m, priceFeed, endTimestamp } = await feedMarket();
+    setFeed(priceFeed, BigInt(210_00000000), BigInt(10000000), endTimestamp + BigInt(10), SystemProgram.programId);
+
+    await expect(resolveFromFeed(m, priceFeed)).rejects.toThrow("Price feed account is invalid");
+  });
+
+  it("Rejects a price with a wide confidence interval", async () => {
+    const { m, priceFeed, endTimestamp } = await feedMarket();
+    setFeed(priceFeed, BigInt(210_00000000), BigInt(5_00000000), endTimestamp + BigInt(10));
+
+    await expect(resolveFromFeed(m, priceFeed)).rejects.toThrow("Price confidence interval is too wide");
+  });
+
+  it("Settles the first outcome when the price is above the threshold", async () => {
+    const { m, priceFeed, endTimestamp } = await feedMarket();
+    setFeed(priceFeed, BigInt(210_00000000), BigInt(10000000), endTimestamp + BigInt(10));
+
+    await resolveFromFeed(m, priceFeed);
+
+    const market = await env.program.account.market.fetch(m.market);
+    expect(market.marketSettled).toBe(true);
+    expect(market.winningOutcome.toNumber()).toBe(0);
+  });
+});
@@ -0,0 +1,200 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as splToken from '@solana/spl-token';
+import {
+  SCALE, TOKEN, TestEnv, TestMarket, buyShares, claimPayout, closeMarket, createMarket, expectVaultMatchesLedgers,
+  fundedWallet, sellShares, setupTestEnv, shareAccount,
+} from './helpers';
+
+describe("Settlement", () => {
+  let env: TestEnv;
+  let trader: Keypair;
+
+  beforeAll(async () => {
+    env = await setupTestEnv();
+    ({ wallet: trader } = await fundedWallet(env, 10_000));
+  });
+
+  describe("Scalar markets", () => {
+    const scalarMarket = () => createMarket(env, {
+      outcomes: ["Long", "Short"],
+      marketType: { scalar: {} },
+      lowerBound: 0,
+      upperBound: 100,
+    });
+
+    it("Pays LONG in full for a value beyond the upper bound", async () => {
+      const m = await scalarMarket();
+      await buyShares(env, m, env.user, 0, 10);
+      await buyShares(env, m, trader, 1, 10);
+      await closeMarket(env, m);
+
+      await env.program.methods
+        .setScalarOutcome(new anchor.BN(150))
+        .accounts({ market: m.market, oracle: env.user.publicKey })
+        .signers([env.user])
+        .rpc();
+
+      const market = await env.program.account.market.fetch(m.market);
+      expect(market.resolvedValue.toNumber()).toBe(100);
+
+      expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(10 * TOKEN));
+      expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(0));
+    });
+
+    it("Splits the payout evenly at the midpoint", async () => {
+      const m = await scalarMarket();
+      await buyShares(env, m, env.user, 0, 10);
+      await buyShares(env, m, trader, 1, 10);
+      await closeMarket(env, m);
+
+      await env.program.methods
+        .setScalarOutcome(new anchor.BN(50))
+        .accounts({ market: m.market, oracle: env.user.publicKey })
+        .signers([env.user])
+        .rpc();
+
+      expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(5 * TOKEN));
+      expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(5 * TOKEN));
+    });
+  });
+
+  describe("Payout vectors", () => {
+    const reportPayouts = (market: anchor.web3.PublicKey, payoutNumerators: number[]) =>
+      env.program.methods
+        .reportPayouts(payoutNumerators.map((n) => new anchor.BN(n)))
+        .accounts({ market, oracle: env.user.publicKey })
+        .signers([env.user])
+        .rpc();
+
+    it("Rejects vectors of the wrong length or summing to zero", async () => {
+      const m = await createMarket(env);
+      await closeMarket(env, m);
+
+      await expect(reportPayouts(m.market, [1])).rejects.toThrow("Payouts need one numerator per outcome and a non-zero sum");
+      await expect(reportPayouts(m.market, [1, 2, 3])).rejects.toThrow("Payouts need one numerator per outcome and a non-zero sum");
+      await expect(reportPayouts(m.market, [0, 0])).rejects.toThrow("Payouts need one numerator per outcome and a non-zero sum");
+    });
+
+    it("Pays each outcome its fraction of a [1, 3] vector", async () => {
+      const m = await createMarket(env);
+      await buyShares(env, m, env.user, 0, 20);
+      await buyShares(env, m, trader, 1, 20);
+      await closeMarket(env, m);
+
+      await reportPayouts(m.market, [1, 3]);
+
+      expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(20 * TOKEN / 4));
+      expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(20 * TOKEN * 3 / 4));
+    });
+  });
+
+  describe("Invalidation", () => {
+    it("Refunds holders at the last prices", async () => {
+      const b = 100;
+      const m = await createMarket(env, { b });
+      await buyShares(env, m, env.user, 0, 30);
+      await buyShares(env, m, trader, 1, 10);
+
+      // A cancelled event is invalidated while trading is still open
+      await env.program.methods
+        .invalidateMarket()
+        .accounts({ market: m.market, oracle: env.user.publicKey })
+        .signers([env.user])
+        .rpc();
+
+      const market = await env.program.account.market.fetch(m.market);
+      expect(market.marketInvalid).toBe(true);
+      expect(market.marketClosed).toBe(true);
+
+      // The numerators are the LMSR prices at q = [30, 10]
+      const weights = [Math.exp(30 / b), Math.exp(10 / b)];
+      const numerators = market.payoutNumerators.map((n) => n.toNumber());
+      weights.forEach((weight, i) => {
+        const price = Math.round((weight / (weights[0] + weights[1])) * SCALE);
+        expect(Math.abs(numerators[i] - price)).toBeLessThanOrEqual(1);
+      });
+
+      const denominator = BigInt(market.payoutDenominator.toString());
+      const payoutPerShare = (i: number) => (BigInt(TOKEN) * BigInt(numerators[i])) / denominator;
+      expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(30) * payoutPerShare(0));
+      expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(10) * payoutPerShare(1));
+    });
+  });
+
+  describe("Share ledger", () => {
+    // The vault matches the fee and market maker ledgers, and every outstanding share is
+    // backed by a minted token
+    const expectLedgersMatch = async (m: TestMarket) => {
+      await expectVaultMatchesLedgers(env.program, m.market, m.marketTokenAccount);
+
+      const market = await env.program.account.market.fetch(m.market);
+
+      for (const [i, outcome] of market.outcomes.entries()) {
+        const mint = await splToken.getMint(env.provider.connection, m.outcomeMints[i]);
+        expect(mint.supply.toString()).toBe(outcome.totalShares.toString());
+      }
+    };
+
+    it("Rejects a buy that mints another outcome's shares", async () => {
+      const m = await createMarket(env);
+
+      await expect(
+        buyShares(env, m, trader, 1, 10, {
+          outcomeMint: m.outcomeMints[0],
+          buyerShareAccount: await shareAccount(env, m.outcomeMints[0], trader.publicKey),
+        })
+      ).rejects.toThrow("Invalid outcome index");
+    });
+
+    it("Rejects trades on an outcome index past the last outcome", async () => {
+      const m = await createMarket(env);
+      await buyShares(env, m, trader, 0, 10);
+      // Index 2 resolves to the first outcome's mint in the helpers
+      const pastEnd = { ...m, outcomeMints: [...m.outcomeMints, m.outcomeMints[0]] };
+
+      await expect(buyShares(env, pastEnd, trader, 2, 10)).rejects.toThrow("Invalid outcome index");
+      await expect(sellShares(env, pastEnd, trader, 2, 10)).rejects.toThrow("Invalid outcome index");
+    });
+
+    it("Keeps the vault and mint supplies equal to the ledgers across claims", async () => {
+      const m = await createMarket(env);
+      await buyShares(env, m, env.user, 0, 20);
+      await buyShares(env, m, trader, 1, 15);
+      await sellShares(env, m, env.user, 0, 5);
+      await expectLedgersMatch(m);
+
+      await closeMarket(env, m);
+      await env.program.methods
+        .proposeOutcome(new anchor.BN(0))
+        .accounts({ market: m.market, oracle: env.user.publicKey })
+        .signers([env.user])
+        .rpc();
+
+      expect(await claimPayout(env, m, env.user, 0)).toBe(BigInt(15 * TOKEN));
+      await expectLedgersMatch(m);
+      expect(await claimPayout(env, m, trader, 1)).toBe(BigInt(0));
+      await expectLedgersMatch(m);
+    });
+
+    it("Refuses to settle against a vault that cannot cover the payouts", async () => {
+      const m = await createMarket(env);
+      await buyShares(env, m, trader, 0, 10);
+      await closeMarket(env, m);
+
+      // Empty the vault behind the program's back
+      const vault = await env.provider.connection.getAccountInfo(m.marketTokenAccount);
+      const data = Buffer.from(vault!.data);
+      data.writeBigUInt64LE(BigInt(0), 64);
+      env.context.setAccount(m.marketTokenAccount, { ...vault!, data });
+
+      await expect(
+        env.program.methods
+          .proposeOutcome(new anchor.BN(0))
+          .accounts({ market: m.market, oracle: env.user.publicKey })
+          .signers([env.user])
+          .rpc()
+      ).rejects.toThrow("Market vault cannot cover its obligations");
+    });
+  });
+});